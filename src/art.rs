@@ -0,0 +1,127 @@
+use crate::card::Card;
+
+/// Size of the boxes drawn by `Card::to_ascii_art` and `render_cards`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArtStyle {
+    /// Three lines, value and suit on the middle one.
+    Compact,
+    /// Five lines, value in two corners and the suit in the middle.
+    #[default]
+    Full,
+}
+
+impl Card {
+    /// The card as a small box, lines separated by `\n`.
+    pub fn to_ascii_art(&self, style: ArtStyle) -> String {
+        card_lines(Some(*self), style).join("\n")
+    }
+}
+
+/// Cards side by side, one space apart. `None` is drawn face down.
+///
+/// ```
+/// use poker::{art::{render_cards, ArtStyle}, card::Card};
+///
+/// let ace = Card::try_from("As").unwrap();
+/// assert_eq!(
+///     render_cards(&[Some(ace), None], ArtStyle::Compact),
+///     "+---+ +---+\n|A♠ | |###|\n+---+ +---+"
+/// );
+/// ```
+pub fn render_cards<C: Into<Option<Card>> + Copy>(cards: &[C], style: ArtStyle) -> String {
+    let boxes: Vec<Vec<String>> = cards.iter().map(|&c| card_lines(c.into(), style)).collect();
+    let height = boxes.first().map_or(0, |b| b.len());
+    (0..height)
+        .map(|row| {
+            boxes
+                .iter()
+                .map(|b| b[row].as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn card_lines(card: Option<Card>, style: ArtStyle) -> Vec<String> {
+    let line = |s: &str| s.to_string();
+    match (style, card) {
+        (ArtStyle::Compact, Some(card)) => vec![
+            line("+---+"),
+            format!("|{:<3}|", format!("{}{:#}", card.value(), card.suit())),
+            line("+---+"),
+        ],
+        (ArtStyle::Compact, None) => vec![line("+---+"), line("|###|"), line("+---+")],
+        (ArtStyle::Full, Some(card)) => vec![
+            line("+-----+"),
+            format!("|{:<5}|", card.value()),
+            format!("|  {:#}  |", card.suit()),
+            format!("|{:>5}|", card.value()),
+            line("+-----+"),
+        ],
+        (ArtStyle::Full, None) => vec![
+            line("+-----+"),
+            line("|#####|"),
+            line("|#####|"),
+            line("|#####|"),
+            line("+-----+"),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        Card::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn test_card_art() {
+        assert_eq!(
+            card("As").to_ascii_art(ArtStyle::Full),
+            "+-----+\n\
+             |A    |\n\
+             |  ♠  |\n\
+             |    A|\n\
+             +-----+"
+        );
+        assert_eq!(
+            card("10h").to_ascii_art(ArtStyle::Full),
+            "+-----+\n\
+             |10   |\n\
+             |  ♥  |\n\
+             |   10|\n\
+             +-----+"
+        );
+        assert_eq!(
+            card("10h").to_ascii_art(ArtStyle::Compact),
+            "+---+\n\
+             |10♥|\n\
+             +---+"
+        );
+    }
+
+    #[test]
+    fn test_render_cards() {
+        let board = [card("As"), card("10h"), card("7c")];
+        assert_eq!(
+            render_cards(&board, ArtStyle::Full),
+            "+-----+ +-----+ +-----+\n\
+             |A    | |10   | |7    |\n\
+             |  ♠  | |  ♥  | |  ♣  |\n\
+             |    A| |   10| |    7|\n\
+             +-----+ +-----+ +-----+"
+        );
+        assert_eq!(
+            render_cards(&[Some(card("Kd")), None], ArtStyle::Full),
+            "+-----+ +-----+\n\
+             |K    | |#####|\n\
+             |  ♦  | |#####|\n\
+             |    K| |#####|\n\
+             +-----+ +-----+"
+        );
+        assert_eq!(render_cards::<Card>(&[], ArtStyle::Compact), "");
+    }
+}
@@ -0,0 +1,179 @@
+//! How the cards in one hand take combos out of another player's range.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    card::Card, error::Error, holdem::check_duplicates, preflop::HoleClass, range::Range,
+    HoldemHand, RankCategory,
+};
+
+/// The villain combos making one category on the board, weighted as in the
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CategoryBlocking {
+    pub category: RankCategory,
+    /// Combos not holding a board card.
+    pub before: f64,
+    /// Those of them not holding a hero card either.
+    pub after: f64,
+}
+
+impl CategoryBlocking {
+    pub fn blocked(&self) -> f64 {
+        self.before - self.after
+    }
+
+    /// The share of the category's combos blocked, 0 when it has none.
+    pub fn reduction(&self) -> f64 {
+        if self.before == 0.0 {
+            0.0
+        } else {
+            self.blocked() / self.before
+        }
+    }
+
+    /// Hero holds none of the cards the category's combos use.
+    pub fn is_unblocked(&self) -> bool {
+        self.blocked() == 0.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockerReport {
+    /// The categories the range makes on the board, strongest first.
+    pub categories: Vec<CategoryBlocking>,
+    /// The weight of combos blocked in each class, most blocked first,
+    /// leaving out classes with none.
+    pub classes: Vec<(HoleClass, f64)>,
+}
+
+impl BlockerReport {
+    pub fn category(&self, category: RankCategory) -> Option<&CategoryBlocking> {
+        self.categories.iter().find(|c| c.category == category)
+    }
+
+    pub fn unblocked(&self) -> impl Iterator<Item = RankCategory> + '_ {
+        self.categories
+            .iter()
+            .filter(|c| c.is_unblocked())
+            .map(|c| c.category)
+    }
+}
+
+/// What `hero` blocks of `villain` on a board of three to five cards.
+///
+/// ```
+/// use poker::{blockers::blocker_effects, card::Card, range::Range, RankCategory};
+///
+/// let cards = Card::parse_many("Ah 2c 9h 7h 3h").unwrap();
+/// let villain = Range::parse("AKs, KQs, 99").unwrap();
+/// let report = blocker_effects([cards[0], cards[1]], &villain, &cards[2..]).unwrap();
+/// let flushes = report.category(RankCategory::Flush).unwrap();
+/// assert_eq!((flushes.before, flushes.after), (2.0, 1.0));
+/// ```
+pub fn blocker_effects(
+    hero: [Card; 2],
+    villain: &Range,
+    board: &[Card],
+) -> Result<BlockerReport, Error> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(Error::BadCard(format!(
+            "invalid board of {} cards",
+            board.len()
+        )));
+    }
+    check_duplicates(&[&hero[..], board].concat())?;
+
+    let mut categories = BTreeMap::new();
+    let mut classes = BTreeMap::new();
+    let mut cards = board.to_vec();
+    for (combo, weight) in villain.iter_weighted() {
+        if combo.iter().any(|c| board.contains(c)) {
+            continue;
+        }
+        cards.extend(combo);
+        let category = HoldemHand::best_from(&cards)?.rank().category();
+        cards.truncate(board.len());
+
+        let counts = categories.entry(category).or_insert((0.0, 0.0));
+        counts.0 += weight;
+        if combo.iter().any(|c| hero.contains(c)) {
+            *classes.entry(HoleClass::from_cards(combo)).or_insert(0.0) += weight;
+        } else {
+            counts.1 += weight;
+        }
+    }
+
+    let mut classes: Vec<_> = classes.into_iter().collect();
+    classes.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    Ok(BlockerReport {
+        categories: categories
+            .into_iter()
+            .rev()
+            .map(|(category, (before, after))| CategoryBlocking {
+                category,
+                before,
+                after,
+            })
+            .collect(),
+        classes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Value;
+
+    fn report(hero: &str, villain: &str, board: &str) -> BlockerReport {
+        let hero = Card::parse_many(hero).unwrap();
+        let board = Card::parse_many(board).unwrap();
+        let villain = Range::parse(villain).unwrap();
+        blocker_effects([hero[0], hero[1]], &villain, &board).unwrap()
+    }
+
+    #[test]
+    fn test_flush_blockers() {
+        let villain = "22+, A2s+, K9s+, QTs+, JTs, T9s, 98s, 87s, AJo+, KQo";
+        let ace = report("Ah Kd", villain, "9h 7h 2h");
+        let flushes = ace.category(RankCategory::Flush).unwrap();
+        // nine of the fifteen heart flushes hold the ace
+        assert_eq!((flushes.before, flushes.after), (15.0, 6.0));
+        assert!(flushes.reduction() > 0.5);
+        let ako = HoleClass::offsuit(Value::Ace, Value::King).unwrap();
+        assert_eq!(ace.classes[0], (ako, 5.0));
+
+        // offsuit rags only touch 44, 33, Ac4c and Ad3d
+        let rags = report("4c 3d", villain, "9h 7h 2h");
+        assert!(rags.category(RankCategory::Flush).unwrap().is_unblocked());
+        assert!(rags.category(RankCategory::Set).unwrap().is_unblocked());
+        let blocked: f64 = rags.categories.iter().map(|c| c.blocked()).sum();
+        let total: f64 = rags.categories.iter().map(|c| c.before).sum();
+        assert_eq!(blocked, 8.0);
+        assert!(blocked / total < 0.05);
+    }
+
+    #[test]
+    fn test_categories() {
+        let report = report("Kc Qc", "99, 22, AA, KQo, K9o", "9h 2d Ks");
+        let categories: Vec<_> = report.categories.iter().map(|c| c.category).collect();
+        use RankCategory::*;
+        assert_eq!(categories, [Set, TwoPair, Pair]);
+        let sets = report.category(Set).unwrap();
+        assert_eq!((sets.before, sets.after), (6.0, 6.0));
+        // K9o without the Ks and 9h, then without the Kc
+        let two_pair = report.category(TwoPair).unwrap();
+        assert_eq!((two_pair.before, two_pair.after), (7.0, 5.0));
+        // AA and KQo
+        let pairs = report.category(Pair).unwrap();
+        assert_eq!((pairs.before, pairs.after), (6.0 + 9.0, 6.0 + 4.0));
+        assert_eq!(report.unblocked().collect::<Vec<_>>(), [Set]);
+        let kqo = HoleClass::offsuit(Value::King, Value::Queen).unwrap();
+        let k9o = HoleClass::offsuit(Value::King, Value::Nine).unwrap();
+        assert_eq!(report.classes, [(kqo, 5.0), (k9o, 2.0)]);
+
+        let hero = Card::parse_many("Kc Qc").unwrap();
+        let board = Card::parse_many("9h 2d").unwrap();
+        assert!(blocker_effects([hero[0], hero[1]], &Range::new(), &board).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Reading a flop, turn or river board on its own, before any hole cards.
+
+use crate::{
+    card::{Card, Value},
+    error::Error,
+    holdem::check_duplicates,
+};
+
+/// How the board's cards share suits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuitPattern {
+    /// Every card of one suit.
+    Monotone,
+    /// Some cards sharing a suit but not all, so also `Ah Kh Qh 2d 3c`.
+    TwoTone,
+    /// No two cards of one suit, which takes four suits on the turn and
+    /// can't happen on the river.
+    Rainbow,
+}
+
+/// The most copies of one value on the board, and how they group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    DoublePaired,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoardTexture {
+    pub suit_pattern: SuitPattern,
+    pub pairing: Pairing,
+    /// Most distinct values within any five consecutive values, the ace
+    /// counting high and low: 3 for `9 8 6`, 1 for `7 7 2`.
+    pub max_connectivity: u8,
+    pub high_card: Value,
+    /// Some two hole cards make a straight with the board.
+    pub straight_possible: bool,
+    /// Some two hole cards make a flush with the board.
+    pub flush_possible: bool,
+}
+
+/// The texture of a board of three to five distinct cards.
+///
+/// ```
+/// use poker::{board::{analyze_board, Pairing, SuitPattern}, card::Card};
+///
+/// let texture = analyze_board(&Card::parse_many("7h 7d 2c").unwrap()).unwrap();
+/// assert_eq!(texture.pairing, Pairing::Paired);
+/// assert_eq!(texture.suit_pattern, SuitPattern::Rainbow);
+/// assert!(!texture.straight_possible);
+/// ```
+pub fn analyze_board(board: &[Card]) -> Result<BoardTexture, Error> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(Error::BadCard(format!(
+            "invalid board of {} cards",
+            board.len()
+        )));
+    }
+    check_duplicates(board)?;
+
+    let mut suits = [0u8; 4];
+    let mut values = [0u8; 15];
+    for card in board {
+        suits[card.suit().index() as usize] += 1;
+        values[card.value() as usize] += 1;
+    }
+    let suit_pattern = match suits.iter().max() {
+        Some(&n) if n as usize == board.len() => SuitPattern::Monotone,
+        Some(&1) => SuitPattern::Rainbow,
+        _ => SuitPattern::TwoTone,
+    };
+
+    let mut groups: Vec<u8> = values.iter().copied().filter(|&n| n > 1).collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let pairing = match groups[..] {
+        [] => Pairing::Unpaired,
+        [4, ..] => Pairing::Quads,
+        [3, 2, ..] => Pairing::FullHouse,
+        [3, ..] => Pairing::Trips,
+        [2, 2, ..] => Pairing::DoublePaired,
+        _ => Pairing::Paired,
+    };
+
+    // index 1 is the ace played low
+    values[1] = values[Value::Ace as usize];
+    let max_connectivity = (1..=10)
+        .map(|low| (low..low + 5).filter(|&v| values[v] > 0).count() as u8)
+        .max()
+        .unwrap_or(0);
+
+    Ok(BoardTexture {
+        suit_pattern,
+        pairing,
+        max_connectivity,
+        high_card: board.iter().map(Card::value).max().unwrap_or(Value::Two),
+        straight_possible: max_connectivity >= 3,
+        flush_possible: suits.iter().any(|&n| n >= 3),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(s: &str) -> BoardTexture {
+        analyze_board(&Card::parse_many(s).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_flops() {
+        let broadway = texture("As Ks Qs");
+        assert_eq!(broadway.suit_pattern, SuitPattern::Monotone);
+        assert_eq!(broadway.pairing, Pairing::Unpaired);
+        assert_eq!(broadway.high_card, Value::Ace);
+        assert_eq!(broadway.max_connectivity, 3);
+        assert!(broadway.straight_possible && broadway.flush_possible);
+
+        let paired = texture("7h 7d 2c");
+        assert_eq!(paired.suit_pattern, SuitPattern::Rainbow);
+        assert_eq!(paired.pairing, Pairing::Paired);
+        assert_eq!(paired.max_connectivity, 1);
+        assert!(!paired.straight_possible && !paired.flush_possible);
+
+        // the 9 8 6 fit in 5-9, one value missing
+        let connected = texture("9c 8d 6h");
+        assert_eq!(connected.max_connectivity, 3);
+        assert!(connected.straight_possible);
+        assert_eq!(connected.suit_pattern, SuitPattern::Rainbow);
+        assert_eq!(texture("9c 8c 6h").suit_pattern, SuitPattern::TwoTone);
+
+        // the wheel counts the ace low
+        assert_eq!(texture("Ah 3d 5c").max_connectivity, 3);
+        assert_eq!(texture("Ah 8d 2c").max_connectivity, 2);
+        assert_eq!(texture("Kh 7d 2c").max_connectivity, 1);
+    }
+
+    #[test]
+    fn test_turns_and_rivers() {
+        let turn = texture("9h 9d 4c 4s");
+        assert_eq!(turn.pairing, Pairing::DoublePaired);
+        assert_eq!(turn.suit_pattern, SuitPattern::Rainbow);
+        assert_eq!(texture("9h 9d 9c 4s").pairing, Pairing::Trips);
+        assert_eq!(texture("9h 9d 9c 4s 4h").pairing, Pairing::FullHouse);
+        assert_eq!(texture("9h 9d 9c 9s 4h").pairing, Pairing::Quads);
+
+        let river = texture("2h 3h 4h 5h 6d");
+        assert_eq!(river.suit_pattern, SuitPattern::TwoTone);
+        assert_eq!(river.max_connectivity, 5);
+        assert!(river.flush_possible);
+
+        // the pattern goes by the most cards of one suit
+        let three_suits = texture("Ah Kh Qh 2d 3c");
+        assert_eq!(three_suits.suit_pattern, SuitPattern::TwoTone);
+        assert!(three_suits.flush_possible);
+        assert_eq!(texture("2h 3h 4d 5c").suit_pattern, SuitPattern::TwoTone);
+        assert_eq!(texture("2h 3d 4c 5s").suit_pattern, SuitPattern::Rainbow);
+        assert_eq!(texture("2h 3h 4h 5h").suit_pattern, SuitPattern::Monotone);
+        assert_eq!(texture("2h 3d 4c 5s 6h").suit_pattern, SuitPattern::TwoTone);
+        assert_eq!(
+            texture("2c 5c 9c Jc Ac").suit_pattern,
+            SuitPattern::Monotone
+        );
+
+        let err = analyze_board(&Card::parse_many("2h 3h").unwrap());
+        assert_eq!(
+            err,
+            Err(Error::BadCard("invalid board of 2 cards".to_string()))
+        );
+        let err = analyze_board(&Card::parse_many("2h 3h 2h").unwrap());
+        assert!(matches!(err, Err(Error::DuplicateCard(_))));
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{
+    card::{Card, CardSet},
+    fast,
+};
+
+const SHARDS: usize = 16;
+
+/// Memoizes a seven-card evaluator by the `CardSet` mask of the cards.
+///
+/// The cache is split into shards, each behind its own lock, so one cache
+/// can be shared by reference across threads. Each shard drops its oldest
+/// entry when full, keeping at most `capacity` entries in all.
+///
+/// ```
+/// use poker::{cache::EvalCache, card::Card, fast};
+///
+/// let cache = EvalCache::new(1000);
+/// let cards: [Card; 7] = Card::parse_many("As Ks Qs Js 10s 2c 2d")
+///     .unwrap()
+///     .try_into()
+///     .unwrap();
+/// assert_eq!(cache.eval7(&cards), fast::eval7(&cards));
+/// assert_eq!(cache.eval7(&cards), fast::eval7(&cards));
+/// assert_eq!((cache.hits(), cache.misses()), (1, 1));
+/// ```
+pub struct EvalCache<E = fn(&[Card; 7]) -> u16> {
+    evaluate: E,
+    shards: Vec<Mutex<Shard>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct Shard {
+    capacity: usize,
+    entries: HashMap<u64, u16>,
+    // keys in insertion order, oldest first
+    order: VecDeque<u64>,
+}
+
+impl EvalCache {
+    /// A cache over `fast::eval7`.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_evaluator(fast::eval7, capacity)
+    }
+}
+
+impl<E: Fn(&[Card; 7]) -> u16> EvalCache<E> {
+    /// A cache over any evaluator, e.g. `LookupTable::eval7` in a closure.
+    pub fn with_evaluator(evaluate: E, capacity: usize) -> Self {
+        let count = capacity.clamp(1, SHARDS);
+        let shards = (0..count)
+            .map(|i| {
+                let capacity = capacity / count + usize::from(i < capacity % count);
+                Mutex::new(Shard {
+                    capacity,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        EvalCache {
+            evaluate,
+            shards,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The evaluator's result for `cards`, from the cache when present.
+    pub fn eval7(&self, cards: &[Card; 7]) -> u16 {
+        let key = cards.iter().copied().collect::<CardSet>().mask();
+        let shard = &self.shards[shard_of(key, self.shards.len())];
+        if let Some(&index) = lock(shard).entries.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return index;
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        // evaluated unlocked; two threads may both miss on a key and insert
+        // the same value
+        let index = (self.evaluate)(cards);
+        let mut shard = lock(shard);
+        if shard.capacity > 0 && shard.entries.insert(key, index).is_none() {
+            shard.order.push_back(key);
+            if shard.order.len() > shard.capacity {
+                if let Some(oldest) = shard.order.pop_front() {
+                    shard.entries.remove(&oldest);
+                }
+            }
+        }
+        index
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Most entries the cache holds at once.
+    pub fn capacity(&self) -> usize {
+        self.shards.iter().map(|s| lock(s).capacity).sum()
+    }
+
+    /// Entries held now.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| lock(s).entries.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every entry and resets the counters.
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            let mut shard = lock(shard);
+            shard.entries.clear();
+            shard.order.clear();
+        }
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+// a panic elsewhere cannot leave a shard inconsistent, so poisoning is ignored
+fn lock(shard: &Mutex<Shard>) -> std::sync::MutexGuard<'_, Shard> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn shard_of(key: u64, shards: usize) -> usize {
+    // card masks differ mostly in a few bits; mix them across the word
+    (key.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32) as usize % shards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::sample_deals;
+
+    #[test]
+    fn test_hits() {
+        let hands = sample_deals::<7>(21, 500);
+        let cache = EvalCache::new(10_000);
+        for cards in &hands {
+            assert_eq!(cache.eval7(cards), fast::eval7(cards));
+        }
+        assert_eq!((cache.hits(), cache.misses()), (0, 500));
+        for cards in &hands {
+            let mut reordered = *cards;
+            reordered.reverse();
+            assert_eq!(cache.eval7(&reordered), fast::eval7(cards));
+        }
+        assert_eq!((cache.hits(), cache.misses()), (500, 500));
+        assert_eq!(cache.len(), 500);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let hands = sample_deals::<7>(22, 2000);
+        for capacity in [0, 1, 5, 100] {
+            let cache = EvalCache::with_evaluator(|cards: &[Card; 7]| fast::eval7(cards), capacity);
+            assert_eq!(cache.capacity(), capacity);
+            for cards in &hands {
+                assert_eq!(cache.eval7(cards), fast::eval7(cards));
+                assert!(cache.len() <= capacity);
+            }
+        }
+
+        // the first hand was evicted long ago, the last is still there
+        let cache = EvalCache::new(100);
+        for cards in &hands {
+            cache.eval7(cards);
+        }
+        let misses = cache.misses();
+        cache.eval7(&hands[1999]);
+        assert_eq!(cache.misses(), misses);
+        cache.eval7(&hands[0]);
+        assert_eq!(cache.misses(), misses + 1);
+    }
+
+    #[test]
+    fn test_threads() {
+        let hands = sample_deals::<7>(23, 1000);
+        let cache = EvalCache::new(512);
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let (cache, hands) = (&cache, &hands);
+                scope.spawn(move || {
+                    for cards in hands.iter().cycle().skip(t * 100).take(3000) {
+                        assert_eq!(cache.eval7(cards), fast::eval7(cards));
+                    }
+                });
+            }
+        });
+        assert_eq!(cache.hits() + cache.misses(), 4 * 3000);
+        assert!(cache.len() <= 512);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::card::{Card, Suit};
+
+/// A permutation of the four suits, from original to canonical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuitMapping {
+    // canonical suit of each original suit, indexed by `Suit::index`
+    to: [Suit; 4],
+}
+
+impl SuitMapping {
+    pub const IDENTITY: SuitMapping = SuitMapping { to: Suit::ALL };
+
+    pub fn map(&self, suit: Suit) -> Suit {
+        self.to[suit.index() as usize]
+    }
+
+    pub fn map_card(&self, card: Card) -> Card {
+        Card::new(self.map(card.suit()), card.value())
+    }
+
+    /// The mapping back from canonical to original suits.
+    pub fn inverse(&self) -> SuitMapping {
+        let mut to = Suit::ALL;
+        for suit in Suit::ALL {
+            to[self.map(suit).index() as usize] = suit;
+        }
+        SuitMapping { to }
+    }
+}
+
+impl Default for SuitMapping {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Relabels suits so that hands differing only by a suit permutation
+/// compare equal.
+///
+/// Suits are renamed in order of first appearance in `cards`: the first
+/// suit seen becomes heart, the next diamond, then club, then spade (the
+/// `Suit::ALL` order). Suits that never appear take the remaining
+/// canonical suits in `Suit::ALL` order. Card order is kept, so callers
+/// wanting an order-independent key should sort first.
+pub fn canonicalize(cards: &[Card]) -> (Vec<Card>, SuitMapping) {
+    let mut to: [Option<Suit>; 4] = [None; 4];
+    let mut next = 0;
+    let unseen = Suit::ALL.into_iter();
+    for suit in cards.iter().map(|c| c.suit()).chain(unseen) {
+        let slot = &mut to[suit.index() as usize];
+        if slot.is_none() {
+            *slot = Some(Suit::ALL[next]);
+            next += 1;
+        }
+    }
+    let mapping = SuitMapping {
+        to: to.map(|s| s.expect("every suit is assigned")),
+    };
+    let canonical = cards.iter().map(|&c| mapping.map_card(c)).collect();
+    (canonical, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canon(s: &str) -> (String, SuitMapping) {
+        let (cards, mapping) = canonicalize(&Card::parse_many(s).unwrap());
+        let cards = cards.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        (cards.join(" "), mapping)
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let (first, _) = canon("Ah Kh 2c");
+        let (second, mapping) = canon("As Ks 2d");
+        assert_eq!(first, "Ah Kh 2d");
+        assert_eq!(first, second);
+        assert_eq!(mapping.map(Suit::Spade), Suit::Heart);
+        assert_eq!(mapping.map(Suit::Diamond), Suit::Diamond);
+        assert_eq!(mapping.map(Suit::Heart), Suit::Club);
+        assert_eq!(mapping.map(Suit::Club), Suit::Spade);
+
+        assert_eq!(canon("Qs 7c 2d").0, "Qh 7d 2c");
+        assert_eq!(canon(&first), (first, SuitMapping::IDENTITY));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let cards = Card::parse_many("Kc 9c 4s Ad 4h").unwrap();
+        let (canonical, mapping) = canonicalize(&cards);
+        let back = mapping.inverse();
+        let restored: Vec<Card> = canonical.iter().map(|&c| back.map_card(c)).collect();
+        assert_eq!(restored, cards);
+        assert_eq!(back.inverse(), mapping);
+    }
+}
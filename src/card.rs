@@ -1,31 +1,17 @@
 #![allow(unused_imports)]
-use crate::error::Error;
-use once_cell::sync::Lazy;
+use crate::{error::Error, holdem::HoldemHand};
 use std::{
-    collections::HashMap,
+    collections::HashSet,
     fmt::{Display, Formatter},
+    num::NonZeroU8,
+    str::FromStr,
 };
 
-const SUIT_STRINGS: [&str; 4] = ["h", "d", "c", "s"];
-const VALUE_STRINGS: [&str; 13] = [
-    "a", "2", "3", "4", "5", "6", "7", "8", "9", "10", "j", "q", "k",
-];
-
-static SUIT_LOOKUP: Lazy<HashMap<&'static str, Suit>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-    SUIT_STRINGS.iter().enumerate().for_each(|(i, &s)| {
-        m.insert(s, Suit::values()[i]);
-    });
-    m
-});
-
-static VALUE_LOOKUP: Lazy<HashMap<&'static str, Value>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-    VALUE_STRINGS.iter().enumerate().for_each(|(i, &s)| {
-        m.insert(s, Value::values()[i]);
-    });
-    m
-});
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Black,
+}
 
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Suit {
@@ -35,30 +21,198 @@ pub enum Suit {
     Spade,
 }
 impl Suit {
+    /// All suits in declaration order, matching `suit as u8`.
+    pub const ALL: [Suit; 4] = [Suit::Heart, Suit::Diamond, Suit::Club, Suit::Spade];
+    pub const COUNT: usize = 4;
+
+    #[deprecated(note = "use `Suit::ALL`")]
     pub fn values() -> [Self; 4] {
-        [Suit::Heart, Suit::Diamond, Suit::Club, Suit::Spade]
+        Self::ALL
+    }
+
+    /// Case-insensitive `h`, `d`, `c` or `s`.
+    const fn from_ascii(b: u8) -> Option<Suit> {
+        match b.to_ascii_lowercase() {
+            b'h' => Some(Suit::Heart),
+            b'd' => Some(Suit::Diamond),
+            b'c' => Some(Suit::Club),
+            b's' => Some(Suit::Spade),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Suit::Heart => 'h',
+            Suit::Diamond => 'd',
+            Suit::Club => 'c',
+            Suit::Spade => 's',
+        }
+    }
+
+    /// Parses a single ASCII suit letter; glyphs need `TryFrom<&str>`.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Suit, Error> {
+        match bytes {
+            [b] => Suit::from_ascii(*b),
+            _ => None,
+        }
+        .ok_or_else(|| Error::BadSuit(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    pub fn from_char(c: char) -> Result<Suit, Error> {
+        Suit::try_from(c)
+    }
+
+    pub fn iter() -> impl Iterator<Item = Suit> {
+        Suit::ALL.into_iter()
+    }
+
+    /// Position in `Suit::ALL`: heart 0, diamond 1, club 2, spade 3.
+    pub const fn index(self) -> u8 {
+        match self {
+            Suit::Heart => 0,
+            Suit::Diamond => 1,
+            Suit::Club => 2,
+            Suit::Spade => 3,
+        }
+    }
+
+    /// Inverse of `Suit::index`.
+    pub const fn from_index(index: u8) -> Result<Suit, Error> {
+        match Suit::checked_from_index(index) {
+            Some(suit) => Ok(suit),
+            None => Err(Error::BadSuitIndex(index)),
+        }
+    }
+
+    /// `Suit::from_index` for const items such as lookup tables: a
+    /// `Result` holding an `Error` can't be dropped in a const, so they
+    /// can't match on it.
+    pub const fn checked_from_index(index: u8) -> Option<Suit> {
+        match index {
+            0..=3 => Some(Suit::ALL[index as usize]),
+            _ => None,
+        }
+    }
+
+    pub fn cmp_with(self, other: Suit, order: SuitOrder) -> std::cmp::Ordering {
+        order.rank(self).cmp(&order.rank(other))
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Suit::Heart | Suit::Diamond => Color::Red,
+            Suit::Club | Suit::Spade => Color::Black,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Suit::Heart => '♥',
+            Suit::Diamond => '♦',
+            Suit::Club => '♣',
+            Suit::Spade => '♠',
+        }
+    }
+}
+
+/// A game-specific ranking of the suits, lowest first.
+///
+/// The derived `Ord` on `Suit` (heart < diamond < club < spade) is what
+/// `SuitOrder::default()` uses; other games can compare with their own
+/// hierarchy through `Suit::cmp_with` and `Card::cmp_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuitOrder {
+    // rank of each suit, indexed by `suit as usize`
+    ranks: [u8; 4],
+}
+
+impl SuitOrder {
+    /// heart < diamond < club < spade, same as `Suit`'s `Ord`.
+    pub const STANDARD: SuitOrder =
+        SuitOrder::from_order([Suit::Heart, Suit::Diamond, Suit::Club, Suit::Spade]);
+    /// club < diamond < heart < spade.
+    pub const BRIDGE: SuitOrder =
+        SuitOrder::from_order([Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade]);
+    /// diamond < club < heart < spade.
+    pub const BIG_TWO: SuitOrder =
+        SuitOrder::from_order([Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade]);
+
+    const fn from_order(order: [Suit; 4]) -> SuitOrder {
+        let mut ranks = [0; 4];
+        let mut i = 0;
+        while i < 4 {
+            ranks[order[i] as usize] = i as u8;
+            i += 1;
+        }
+        SuitOrder { ranks }
+    }
+
+    /// Builds an order from the suits listed lowest first, which must be a
+    /// permutation of all four suits.
+    pub fn custom(order: [Suit; 4]) -> Result<SuitOrder, Error> {
+        if Suit::ALL.iter().any(|s| !order.contains(s)) {
+            return Err(Error::BadSuit(format!(
+                "not a suit permutation: {:?}",
+                order
+            )));
+        }
+        Ok(SuitOrder::from_order(order))
+    }
+
+    pub fn rank(&self, suit: Suit) -> u8 {
+        self.ranks[suit as usize]
+    }
+}
+
+impl Default for SuitOrder {
+    fn default() -> Self {
+        SuitOrder::STANDARD
     }
 }
 
 impl TryFrom<&str> for Suit {
     type Error = Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        SUIT_LOOKUP
-            .get(value.to_lowercase().as_str())
-            .cloned()
-            .ok_or(Error::BadSuit(value.to_string()))
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Suit::try_from(c),
+            _ => Err(Error::BadSuit(value.to_string())),
+        }
     }
 }
 
-#[rustfmt::skip]
+impl TryFrom<char> for Suit {
+    type Error = Error;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let suit = match value {
+            '♥' => Some(Suit::Heart),
+            '♦' => Some(Suit::Diamond),
+            '♣' => Some(Suit::Club),
+            '♠' => Some(Suit::Spade),
+            c if c.is_ascii() => Suit::from_ascii(c as u8),
+            _ => None,
+        };
+        suit.ok_or_else(|| Error::BadSuit(value.to_string()))
+    }
+}
+
+impl FromStr for Suit {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// `{:#}` renders the suit glyph (`♠`) instead of the letter.
 impl Display for Suit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", match self {
-            Suit::Heart => "h",
-            Suit::Diamond => "d",
-            Suit::Club => "c",
-            Suit::Spade => "s",
-        })
+        let c = if f.alternate() {
+            self.symbol()
+        } else {
+            self.to_char()
+        };
+        f.pad(c.encode_utf8(&mut [0; 4]))
     }
 }
 
@@ -81,10 +235,213 @@ pub enum Value {
 }
 
 impl Value {
+    /// All values from Two to Ace, matching the discriminants.
+    #[rustfmt::skip]
+    pub const ALL: [Value; 13] = [
+        Value::Two, Value::Three, Value::Four, Value::Five, Value::Six, Value::Seven,
+        Value::Eight, Value::Nine, Value::Ten, Value::Jack, Value::Queen, Value::King,
+        Value::Ace,
+    ];
+    pub const COUNT: usize = 13;
+
     pub fn value(self) -> u8 {
         self as u8
     }
 
+    /// Position in `Value::ALL`: two 0 up to ace 12.
+    pub const fn index(self) -> u8 {
+        self as u8 - 2
+    }
+
+    /// Inverse of `Value::index`.
+    pub const fn from_index(index: u8) -> Result<Value, Error> {
+        match Value::checked_from_index(index) {
+            Some(value) => Ok(value),
+            None => Err(Error::BadValueIndex(index)),
+        }
+    }
+
+    /// `Value::from_index` for const items, like `Suit::checked_from_index`.
+    pub const fn checked_from_index(index: u8) -> Option<Value> {
+        match index {
+            0..=12 => Some(Value::ALL[index as usize]),
+            _ => None,
+        }
+    }
+
+    /// Single-char code, case-insensitive: `2`-`9`, `t`, `j`, `q`, `k`, `a`.
+    const fn from_ascii(b: u8) -> Option<Value> {
+        use Value::*;
+        Some(match b.to_ascii_lowercase() {
+            b'2' => Two,
+            b'3' => Three,
+            b'4' => Four,
+            b'5' => Five,
+            b'6' => Six,
+            b'7' => Seven,
+            b'8' => Eight,
+            b'9' => Nine,
+            b't' => Ten,
+            b'j' => Jack,
+            b'q' => Queen,
+            b'k' => King,
+            b'a' => Ace,
+            _ => return None,
+        })
+    }
+
+    /// Parses ASCII bytes the same way as `TryFrom<&str>`.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Value, Error> {
+        let v = match bytes {
+            [b'1', b'0'] => Some(Value::Ten),
+            [b] => Value::from_ascii(*b),
+            _ => None,
+        };
+        v.ok_or_else(|| Error::BadValue(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    /// Lenient parse for numeric rank feeds: `11`, `12` and `13` are the
+    /// faces and the ace is `1` and/or `14` depending on `mode`. Anything
+    /// else is parsed like `TryFrom<&str>`.
+    pub fn parse_numeric(s: &str, mode: AceMode) -> Result<Value, Error> {
+        match (s, mode) {
+            ("11", _) => Ok(Value::Jack),
+            ("12", _) => Ok(Value::Queen),
+            ("13", _) => Ok(Value::King),
+            ("1", AceMode::Low | AceMode::Both) => Ok(Value::Ace),
+            ("14", AceMode::High | AceMode::Both) => Ok(Value::Ace),
+            ("1" | "14", _) => Err(Error::BadValue(s.to_string())),
+            _ => Value::try_from(s),
+        }
+    }
+
+    /// Standard single-char rank code, with `T` for ten.
+    pub fn to_char(self) -> char {
+        match self {
+            Value::Ten => 'T',
+            Value::Jack => 'J',
+            Value::Queen => 'Q',
+            Value::King => 'K',
+            Value::Ace => 'A',
+            other => (b'0' + other.value()) as char,
+        }
+    }
+
+    pub fn from_char(c: char) -> Result<Value, Error> {
+        Value::try_from(c)
+    }
+
+    /// Values from `start` to `end` inclusive, ace high.
+    /// Reversed bounds give an empty range.
+    pub fn range(start: Value, end: Value) -> ValueRange {
+        ValueRange {
+            start: start.value(),
+            end: end.value() + 1,
+        }
+    }
+
+    /// Values from `start` up to but excluding `end`.
+    pub fn range_exclusive(start: Value, end: Value) -> ValueRange {
+        ValueRange {
+            start: start.value(),
+            end: end.value(),
+        }
+    }
+
+    /// The value `n` ranks higher, `None` past the ace.
+    pub fn checked_add_rank(self, n: u8) -> Option<Value> {
+        Value::try_from(self.value().checked_add(n)?).ok()
+    }
+
+    /// The value `n` ranks lower, `None` past the two.
+    pub fn checked_sub_rank(self, n: u8) -> Option<Value> {
+        Value::try_from(self.value().checked_sub(n)?).ok()
+    }
+
+    /// The value one rank higher, `None` above the ace.
+    pub fn next(self) -> Option<Value> {
+        self.checked_add_rank(1)
+    }
+
+    /// The value one rank lower, `None` below the two.
+    pub fn prev(self) -> Option<Value> {
+        self.checked_sub_rank(1)
+    }
+
+    /// Like `next`, but the ace wraps around to the two.
+    pub fn next_wrapping(self) -> Value {
+        self.next().unwrap_or(Value::Two)
+    }
+
+    /// Like `prev`, but the two wraps around to the ace.
+    pub fn prev_wrapping(self) -> Value {
+        self.prev().unwrap_or(Value::Ace)
+    }
+
+    /// Jack, Queen or King.
+    pub fn is_face(self) -> bool {
+        matches!(self, Value::Jack | Value::Queen | Value::King)
+    }
+
+    /// Ten through Ace.
+    pub fn is_broadway(self) -> bool {
+        self >= Value::Ten
+    }
+
+    /// Ace through Five, the cards of the wheel straight.
+    pub fn is_wheel_card(self) -> bool {
+        self <= Value::Five || self == Value::Ace
+    }
+
+    /// Absolute distance between the numeric values, ace high.
+    pub fn gap(self, other: Value) -> u8 {
+        self.value().abs_diff(other.value())
+    }
+
+    /// Like `gap`, but also counts the ace low, so `Ace.min_gap(Two) == 1`.
+    pub fn min_gap(self, other: Value) -> u8 {
+        self.gap(other)
+            .min(self.low_value().abs_diff(other.low_value()))
+    }
+
+    /// English name, e.g. `"Six"`.
+    pub fn name(self) -> &'static str {
+        self.names().0
+    }
+
+    /// English plural, e.g. `"Sixes"`.
+    pub fn plural_name(self) -> &'static str {
+        self.names().1
+    }
+
+    fn names(self) -> (&'static str, &'static str) {
+        match self {
+            Value::Two => ("Two", "Twos"),
+            Value::Three => ("Three", "Threes"),
+            Value::Four => ("Four", "Fours"),
+            Value::Five => ("Five", "Fives"),
+            Value::Six => ("Six", "Sixes"),
+            Value::Seven => ("Seven", "Sevens"),
+            Value::Eight => ("Eight", "Eights"),
+            Value::Nine => ("Nine", "Nines"),
+            Value::Ten => ("Ten", "Tens"),
+            Value::Jack => ("Jack", "Jacks"),
+            Value::Queen => ("Queen", "Queens"),
+            Value::King => ("King", "Kings"),
+            Value::Ace => ("Ace", "Aces"),
+        }
+    }
+
+    /// Numeric value with the ace counted as 1.
+    pub fn low_value(self) -> u8 {
+        match self {
+            Value::Ace => 1,
+            other => other.value(),
+        }
+    }
+
+    /// The old Ace-first order, kept for compatibility.
+    #[deprecated(note = "use `Value::ALL`, which is ordered Two to Ace")]
     pub fn values() -> [Value; 13] {
         use Value::*;
         [
@@ -93,6 +450,100 @@ impl Value {
     }
 }
 
+/// Iterator over a run of values, see `Value::range`.
+#[derive(Debug, Clone)]
+pub struct ValueRange {
+    start: u8,
+    end: u8,
+}
+
+impl Iterator for ValueRange {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if self.start >= self.end {
+            return None;
+        }
+        self.start += 1;
+        Value::try_from(self.start - 1).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.saturating_sub(self.start) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for ValueRange {
+    fn next_back(&mut self) -> Option<Value> {
+        if self.start >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Value::try_from(self.end).ok()
+    }
+}
+
+impl ExactSizeIterator for ValueRange {}
+
+/// Which number means ace in `Value::parse_numeric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AceMode {
+    /// Ranks run 1-13, `1` is the ace.
+    Low,
+    /// Ranks run 2-14, `14` is the ace.
+    #[default]
+    High,
+    /// Accept both `1` and `14`.
+    Both,
+}
+
+/// A `Value` compared with the ace low, as in lowball and the wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LowValue(pub Value);
+
+impl LowValue {
+    pub fn value(self) -> u8 {
+        self.0.low_value()
+    }
+}
+
+impl Ord for LowValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+
+impl PartialOrd for LowValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Value> for LowValue {
+    fn from(value: Value) -> Self {
+        LowValue(value)
+    }
+}
+
+impl From<LowValue> for Value {
+    fn from(value: LowValue) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u8> for LowValue {
+    fn eq(&self, other: &u8) -> bool {
+        self.value() == *other
+    }
+}
+
+impl PartialEq<LowValue> for u8 {
+    fn eq(&self, other: &LowValue) -> bool {
+        *self == other.value()
+    }
+}
+
 impl std::ops::Add<u8> for Value {
     type Output = u8;
     fn add(self, rhs: u8) -> Self::Output {
@@ -107,9 +558,36 @@ impl std::ops::Add<Value> for u8 {
     }
 }
 
-impl From<u8> for Value {
-    fn from(value: u8) -> Self {
-        Value::values()[value as usize]
+impl std::ops::Sub<u8> for Value {
+    type Output = u8;
+    fn sub(self, rhs: u8) -> Self::Output {
+        self.value().sub(rhs)
+    }
+}
+
+impl std::ops::Sub<Value> for u8 {
+    type Output = u8;
+    fn sub(self, rhs: Value) -> Self::Output {
+        self.sub(rhs.value())
+    }
+}
+
+/// Same as `Suit::from_index`, which is also usable in const contexts.
+impl TryFrom<u8> for Suit {
+    type Error = Error;
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        Suit::from_index(index)
+    }
+}
+
+/// Maps the numeric value `2..=14` back to a `Value`, matching `Value::value`.
+impl TryFrom<u8> for Value {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Value::ALL
+            .into_iter()
+            .find(|v| v.value() == value)
+            .ok_or(Error::BadValue(value.to_string()))
     }
 }
 
@@ -125,126 +603,1222 @@ impl PartialEq<u8> for Value {
     }
 }
 
+/// Accepts `2`-`10` and `j`, `q`, `k`, `a`, case-insensitively, plus `t`
+/// as the common shorthand for ten.
 impl TryFrom<&str> for Value {
     type Error = Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        VALUE_LOOKUP
-            .get(value.to_lowercase().as_str())
-            .cloned()
-            .ok_or(Error::BadValue(value.to_string()))
+        Value::try_from_bytes(value.as_bytes())
+    }
+}
+
+/// Single-char ranks, so ten is `t` here rather than `10`.
+impl TryFrom<char> for Value {
+    type Error = Error;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let v = if value.is_ascii() {
+            Value::from_ascii(value as u8)
+        } else {
+            None
+        };
+        v.ok_or_else(|| Error::BadValue(value.to_string()))
+    }
+}
+
+impl FromStr for Value {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
     }
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Value::Ace => write!(f, "A"),
-            Value::King => write!(f, "K"),
-            Value::Queen => write!(f, "Q"),
-            Value::Jack => write!(f, "J"),
-            other => write!(f, "{}", other.value()),
+            Value::Ace => f.pad("A"),
+            Value::King => f.pad("K"),
+            Value::Queen => f.pad("Q"),
+            Value::Jack => f.pad("J"),
+            other => f.pad(&other.value().to_string()),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Joker {
     Small,
     Big,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Card(Suit, Value);
-
-impl Card {
-    pub fn new(suit: Suit, value: Value) -> Self {
-        Self(suit, value)
+/// Jokers are written `sj` (small) and `bj` (big), parsed case-insensitively.
+impl TryFrom<&str> for Joker {
+    type Error = Error;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.eq_ignore_ascii_case("sj") {
+            Ok(Joker::Small)
+        } else if value.eq_ignore_ascii_case("bj") {
+            Ok(Joker::Big)
+        } else {
+            Err(Error::BadCard(value.to_string()))
+        }
     }
+}
 
-    pub fn suit(&self) -> Suit {
-        self.0
+impl FromStr for Joker {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
     }
+}
 
-    pub fn value(&self) -> Value {
-        self.1
+impl Display for Joker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Joker::Small => f.pad("sj"),
+            Joker::Big => f.pad("bj"),
+        }
     }
 }
 
-impl TryFrom<&str> for Card {
-    type Error = Error;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card(
+    // `value << 2 | suit`, never zero since values start at 2, so
+    // `Option<Card>` is a single byte too
+    NonZeroU8,
+);
 
-    fn try_from(card: &str) -> Result<Self, Self::Error> {
-        let len = card.len();
-        if len != 2 && len != 3 {
-            return Err(Error::BadCard("invalid length".to_string()));
-        }
-        let (v, s) = card.split_at(len - 1);
-        Ok(Self(Suit::try_from(s)?, Value::try_from(v)?))
+/// Cards order by value first, with the suit only breaking ties
+/// (`2h < 2s < Ah`). This matches the `to_index` order.
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // the packed byte is `value << 2 | suit`, so this is value-major
+        self.0.cmp(&other.0)
     }
 }
 
-impl std::fmt::Display for Card {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.1, self.0)
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Card {
+    /// Every standard card once, in `to_index` order (`2h 2d 2c 2s 3h .. As`).
+    #[rustfmt::skip]
+    pub const ALL: [Card; 52] = {
+        let mut cards = [Card::new(Suit::Heart, Value::Two); 52];
+        let mut i = 0;
+        while i < 52 {
+            cards[i] = Card::new(Suit::ALL[i % 4], Value::ALL[i / 4]);
+            i += 1;
+        }
+        cards
+    };
 
-    #[test]
-    fn test_suit() {
-        assert_eq!(Suit::try_from("h"), Ok(Suit::Heart));
-        assert_eq!(Suit::try_from("d"), Ok(Suit::Diamond));
-        assert_eq!(Suit::try_from("c"), Ok(Suit::Club));
-        assert_eq!(Suit::try_from("s"), Ok(Suit::Spade));
-        assert_eq!(Suit::try_from("H"), Ok(Suit::Heart));
-        assert_eq!(Suit::try_from("D"), Ok(Suit::Diamond));
-        assert_eq!(Suit::try_from("C"), Ok(Suit::Club));
-        assert_eq!(Suit::try_from("S"), Ok(Suit::Spade));
-        assert_eq!(Suit::try_from("x"), Err(Error::BadSuit("x".to_string())));
-        assert_eq!(Suit::try_from(""), Err(Error::BadSuit("".to_string())));
+    pub fn all() -> impl Iterator<Item = Card> {
+        Self::ALL.into_iter()
     }
 
-    #[test]
-    fn test_value() {
-        assert_eq!(Value::try_from("a"), Ok(Value::Ace));
-        assert_eq!(Value::try_from("A"), Ok(Value::Ace));
-        assert_eq!(Value::try_from("2"), Ok(Value::Two));
-        assert_eq!(Value::try_from("10"), Ok(Value::Ten));
-        assert_eq!(
-            Value::try_from("13"),
-            Err(Error::BadValue("13".to_string()))
-        );
-        assert_eq!(Value::try_from("0"), Err(Error::BadValue("0".to_string())));
-        assert_eq!(Value::try_from("1"), Err(Error::BadValue("1".to_string())));
+    pub const fn new(suit: Suit, value: Value) -> Self {
+        match NonZeroU8::new((value as u8) << 2 | suit.index()) {
+            Some(byte) => Self(byte),
+            None => unreachable!(),
+        }
+    }
 
-        // eq
-        assert_ne!(Value::Ace, Value::Two);
-        assert_eq!(Value::Ace, Value::Ace);
-        assert_eq!(Value::Ace, 14);
-        assert_eq!(Value::King, 13);
-        assert_ne!(Value::Ace, 1);
-        assert_ne!(1, Value::Ace);
-        assert_eq!(14, Value::Ace);
+    pub const fn suit(&self) -> Suit {
+        Suit::ALL[(self.0.get() & 0b11) as usize]
+    }
 
-        // ord
-        assert_eq!(Value::Two < Value::Ace, true);
+    pub const fn value(&self) -> Value {
+        Value::ALL[(self.0.get() >> 2) as usize - 2]
+    }
 
-        // Add
-        assert_eq!(Value::Ace + 1, 15);
-        assert_eq!(1 + Value::Ten, 11);
+    /// Compares by value, then by suit under the given order.
+    pub fn cmp_with(&self, other: &Card, order: SuitOrder) -> std::cmp::Ordering {
+        self.value()
+            .cmp(&other.value())
+            .then_with(|| self.suit().cmp_with(other.suit(), order))
+    }
+
+    pub fn is_face(&self) -> bool {
+        self.value().is_face()
+    }
+
+    pub fn is_broadway(&self) -> bool {
+        self.value().is_broadway()
+    }
+
+    pub fn is_wheel_card(&self) -> bool {
+        self.value().is_wheel_card()
+    }
+
+    pub fn same_value(&self, other: &Card) -> bool {
+        self.value() == other.value()
+    }
+
+    pub fn same_suit(&self, other: &Card) -> bool {
+        self.suit() == other.suit()
+    }
+
+    /// Adjacent values, counting the ace both high and low (`A2`, `AK`).
+    pub fn is_connected(&self, other: &Card) -> bool {
+        self.value().min_gap(other.value()) == 1
+    }
+
+    pub fn is_red(&self) -> bool {
+        self.suit().color() == Color::Red
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.suit().color() == Color::Black
+    }
+
+    /// Packs the card into one byte, `value << 2 | suit`.
+    pub fn to_byte(&self) -> u8 {
+        self.0.get()
+    }
+
+    pub fn from_byte(byte: u8) -> Result<Card, Error> {
+        let suit = Suit::ALL[(byte & 0b11) as usize];
+        match Value::try_from(byte >> 2) {
+            Ok(value) => Ok(Card::new(suit, value)),
+            Err(_) => Err(Error::BadCard(format!("invalid byte {}", byte))),
+        }
+    }
+
+    /// Dense id in `0..52`, ordered by value then suit:
+    /// `(value - 2) * 4 + suit`, so `2h` is 0 and `As` is 51.
+    pub fn to_index(&self) -> u8 {
+        (self.value().value() - 2) * 4 + self.suit().index()
+    }
+
+    /// Only `0..52`; the joker indices 52 and 53 need `CardKind::from_index`.
+    pub fn from_index(index: u8) -> Result<Self, Error> {
+        if index >= 52 {
+            return Err(Error::BadCard(format!("invalid index {}", index)));
+        }
+        Ok(Self::new(
+            Suit::ALL[index as usize % 4],
+            Value::ALL[index as usize / 4],
+        ))
+    }
+
+    /// Parses an ASCII token like `b"As"` or `b"10h"` without going through
+    /// `str`. Non-ASCII input is rejected as `Error::BadCard`.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Card, Error> {
+        if !bytes.is_ascii() {
+            return Err(Error::BadCard(String::from_utf8_lossy(bytes).into_owned()));
+        }
+        if bytes.len() != 2 && bytes.len() != 3 {
+            return Err(Error::BadCard("invalid length".to_string()));
+        }
+        let (v, s) = bytes.split_at(bytes.len() - 1);
+        Ok(Card::new(
+            Suit::try_from_bytes(s)?,
+            Value::try_from_bytes(v)?,
+        ))
+    }
+
+    /// Parses a run of cards, with or without separators (whitespace or
+    /// commas), e.g. `"AsKd2c"`, `"As,Kd 2c"` or `"10h9c"`.
+    pub fn parse_many(input: &str) -> Result<Vec<Card>, Error> {
+        let is_sep = |c: char| c.is_whitespace() || c == ',';
+        let mut cards = Vec::new();
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if is_sep(c) {
+                continue;
+            }
+            // the value is one char, except for the two-char "10"
+            if c == '1' && matches!(chars.peek(), Some((_, '0'))) {
+                chars.next();
+            }
+            match chars.next() {
+                Some((i, s)) if !is_sep(s) => {
+                    cards.push(Card::try_from(&input[start..i + s.len_utf8()])?);
+                }
+                next => {
+                    let end = next.map_or(input.len(), |(i, _)| i);
+                    return Err(Error::BadCard(format!(
+                        "incomplete card: {}",
+                        &input[start..end]
+                    )));
+                }
+            }
+        }
+        Ok(cards)
+    }
+
+    /// The single playing-card character from the U+1F0A0 block, e.g. `🂡`.
+    pub fn to_unicode_char(&self) -> char {
+        let base = match self.suit() {
+            Suit::Spade => 0x1F0A0,
+            Suit::Heart => 0x1F0B0,
+            Suit::Diamond => 0x1F0C0,
+            Suit::Club => 0x1F0D0,
+        };
+        // the block has a Knight between Jack and Queen, skip over it
+        let rank = match self.value() {
+            Value::Ace => 1,
+            Value::Queen | Value::King => self.value().value() + 1,
+            other => other.value(),
+        };
+        char::from_u32(base + rank as u32).unwrap()
+    }
+
+    pub fn from_unicode_char(c: char) -> Result<Card, Error> {
+        let bad = || Error::BadCard(c.to_string());
+        let suit = match c as u32 & !0xF {
+            0x1F0A0 => Suit::Spade,
+            0x1F0B0 => Suit::Heart,
+            0x1F0C0 => Suit::Diamond,
+            0x1F0D0 => Suit::Club,
+            _ => return Err(bad()),
+        };
+        let value = match c as u32 & 0xF {
+            1 => Value::Ace,
+            // 12 is the Knight, which has no place in a standard deck
+            rank @ 2..=11 => Value::try_from(rank as u8).map_err(|_| bad())?,
+            rank @ 13..=14 => Value::try_from(rank as u8 - 1).map_err(|_| bad())?,
+            _ => return Err(bad()),
+        };
+        Ok(Card::new(suit, value))
+    }
+
+    /// Parses English card names like `"Ace of Spades"` or `"queen clubs"`,
+    /// case-insensitively and with or without the `of`.
+    pub fn parse_verbose(input: &str) -> Result<Card, Error> {
+        use Value::*;
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let (v, s) = match words[..] {
+            [v, of, s] if of.eq_ignore_ascii_case("of") => (v, s),
+            [v, s] => (v, s),
+            _ => return Err(Error::BadCard(input.to_string())),
+        };
+        let value = match v.to_lowercase().as_str() {
+            "two" => Two,
+            "three" => Three,
+            "four" => Four,
+            "five" => Five,
+            "six" => Six,
+            "seven" => Seven,
+            "eight" => Eight,
+            "nine" => Nine,
+            "ten" => Ten,
+            "jack" => Jack,
+            "queen" => Queen,
+            "king" => King,
+            "ace" => Ace,
+            _ => return Err(Error::BadValue(v.to_string())),
+        };
+        let suit = match s.to_lowercase().trim_end_matches('s') {
+            "heart" => Suit::Heart,
+            "diamond" => Suit::Diamond,
+            "club" => Suit::Club,
+            "spade" => Suit::Spade,
+            _ => return Err(Error::BadSuit(s.to_string())),
+        };
+        Ok(Card::new(suit, value))
+    }
+
+    /// Const parser backing `card!`, ASCII letter suits only.
+    #[doc(hidden)]
+    pub const fn __from_literal(s: &str) -> Option<Card> {
+        let (value, suit) = match s.as_bytes() {
+            [b'1', b'0', s] => (Some(Value::Ten), Suit::from_ascii(*s)),
+            [v, s] => (Value::from_ascii(*v), Suit::from_ascii(*s)),
+            _ => return None,
+        };
+        match (value, suit) {
+            (Some(value), Some(suit)) => Some(Card::new(suit, value)),
+            _ => None,
+        }
+    }
+
+    /// The card as a single bit, `1 << to_index()`. Bits are grouped by
+    /// value, four per value with suits in `Suit::ALL` order, so `2h` is
+    /// bit 0 and `As` bit 51.
+    pub fn mask(&self) -> u64 {
+        1 << self.to_index()
+    }
+
+    pub fn from_mask(mask: u64) -> Result<Card, Error> {
+        if mask.count_ones() != 1 {
+            return Err(Error::BadCard(format!("invalid mask {:#x}", mask)));
+        }
+        Card::from_index(mask.trailing_zeros() as u8)
+    }
+
+    /// Formats the card with `T` for ten, e.g. `Ts` instead of `10s`.
+    pub fn to_compact_string(&self) -> String {
+        self.format_with(CardStyle::Compact)
+    }
+
+    pub fn format_with(&self, style: CardStyle) -> String {
+        match style {
+            CardStyle::Standard => self.to_string(),
+            CardStyle::Compact => format!("{}{}", self.value().to_char(), self.suit()),
+            CardStyle::Uppercase => self.to_string().to_uppercase(),
+            CardStyle::Symbol => format!("{:#}", self),
+        }
+    }
+}
+
+impl TryFrom<&str> for Card {
+    type Error = Error;
+
+    fn try_from(card: &str) -> Result<Self, Self::Error> {
+        // count chars rather than bytes, suit glyphs are multi-byte
+        let len = card.chars().count();
+        if len != 2 && len != 3 {
+            return Err(Error::BadCard("invalid length".to_string()));
+        }
+        let (split, _) = card.char_indices().last().unwrap();
+        let (v, s) = card.split_at(split);
+        Ok(Card::new(Suit::try_from(s)?, Value::try_from(v)?))
+    }
+}
+
+/// Parses a `(value, suit)` pair of chars, e.g. `('T', 's')`.
+impl TryFrom<(char, char)> for Card {
+    type Error = Error;
+
+    fn try_from((value, suit): (char, char)) -> Result<Self, Self::Error> {
+        Ok(Card::new(Suit::try_from(suit)?, Value::try_from(value)?))
+    }
+}
+
+/// Compares only the card's value, the suit is ignored: `As == Value::Ace`
+/// and `Ah == Value::Ace` even though `As != Ah`.
+impl PartialEq<Value> for Card {
+    fn eq(&self, other: &Value) -> bool {
+        self.value() == *other
+    }
+}
+
+impl PartialEq<Card> for Value {
+    fn eq(&self, other: &Card) -> bool {
+        *self == other.value()
+    }
+}
+
+/// Orders by the card's value only, like `PartialEq<Value>`.
+impl PartialOrd<Value> for Card {
+    fn partial_cmp(&self, other: &Value) -> Option<std::cmp::Ordering> {
+        self.value().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<Card> for Value {
+    fn partial_cmp(&self, other: &Card) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&other.value())
+    }
+}
+
+impl From<(Suit, Value)> for Card {
+    fn from((suit, value): (Suit, Value)) -> Self {
+        Card::new(suit, value)
+    }
+}
+
+impl From<(Value, Suit)> for Card {
+    fn from((value, suit): (Value, Suit)) -> Self {
+        Card::new(suit, value)
+    }
+}
+
+impl From<Card> for (Suit, Value) {
+    fn from(card: Card) -> Self {
+        (card.suit(), card.value())
+    }
+}
+
+/// Value first, matching the `Display` order.
+impl From<Card> for (Value, Suit) {
+    fn from(card: Card) -> Self {
+        (card.value(), card.suit())
+    }
+}
+
+impl FromStr for Card {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Builds a `Card` from a literal, checked at compile time.
+///
+/// ```
+/// let card = poker::card!("As");
+/// assert_eq!(card.to_string(), "As");
+/// ```
+///
+/// ```compile_fail
+/// let card = poker::card!("Ax");
+/// ```
+#[macro_export]
+macro_rules! card {
+    ($s:literal) => {{
+        const CARD: $crate::card::Card = match $crate::card::Card::__from_literal($s) {
+            Some(card) => card,
+            None => panic!(concat!("invalid card literal: ", $s)),
+        };
+        CARD
+    }};
+}
+
+/// Builds a `[Card; N]` from literals, each checked at compile time.
+///
+/// ```
+/// let [a, k] = poker::cards!("As", "Kd");
+/// assert!(a.value() > k.value());
+/// ```
+#[macro_export]
+macro_rules! cards {
+    ($($s:literal),* $(,)?) => {
+        [$($crate::card!($s)),*]
+    };
+}
+
+/// Display styles for `Card::format_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CardStyle {
+    /// `As`, `10h`, same as `Display`.
+    #[default]
+    Standard,
+    /// `As`, `Th`.
+    Compact,
+    /// `AS`, `10H`.
+    Uppercase,
+    /// `A♠`, `10♥`, same as `{:#}`.
+    Symbol,
+}
+
+/// `{:#}` renders the suit glyph, e.g. `A♠`.
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = if f.alternate() {
+            format!("{}{:#}", self.value(), self.suit())
+        } else {
+            format!("{}{}", self.value(), self.suit())
+        };
+        f.pad(&s)
+    }
+}
+
+/// Splits cards into those of the given color and the rest, keeping order.
+pub fn filter_color(cards: &[Card], color: Color) -> (Vec<Card>, Vec<Card>) {
+    cards.iter().partition(|c| c.suit().color() == color)
+}
+
+/// Named constants for all 52 cards, e.g. `consts::ACE_OF_SPADES`.
+pub mod consts {
+    use super::{Card, Suit::*, Value::*};
+
+    macro_rules! card_consts {
+        ($($name:ident = $suit:ident, $value:ident, $s:literal;)*) => {
+            $(pub const $name: Card = Card::new($suit, $value);)*
+
+            #[cfg(test)]
+            pub(super) const TABLE: [(Card, &str); 52] = [$(($name, $s)),*];
+        };
+    }
+
+    #[rustfmt::skip]
+    card_consts! {
+        TWO_OF_HEARTS = Heart, Two, "2h";
+        TWO_OF_DIAMONDS = Diamond, Two, "2d";
+        TWO_OF_CLUBS = Club, Two, "2c";
+        TWO_OF_SPADES = Spade, Two, "2s";
+        THREE_OF_HEARTS = Heart, Three, "3h";
+        THREE_OF_DIAMONDS = Diamond, Three, "3d";
+        THREE_OF_CLUBS = Club, Three, "3c";
+        THREE_OF_SPADES = Spade, Three, "3s";
+        FOUR_OF_HEARTS = Heart, Four, "4h";
+        FOUR_OF_DIAMONDS = Diamond, Four, "4d";
+        FOUR_OF_CLUBS = Club, Four, "4c";
+        FOUR_OF_SPADES = Spade, Four, "4s";
+        FIVE_OF_HEARTS = Heart, Five, "5h";
+        FIVE_OF_DIAMONDS = Diamond, Five, "5d";
+        FIVE_OF_CLUBS = Club, Five, "5c";
+        FIVE_OF_SPADES = Spade, Five, "5s";
+        SIX_OF_HEARTS = Heart, Six, "6h";
+        SIX_OF_DIAMONDS = Diamond, Six, "6d";
+        SIX_OF_CLUBS = Club, Six, "6c";
+        SIX_OF_SPADES = Spade, Six, "6s";
+        SEVEN_OF_HEARTS = Heart, Seven, "7h";
+        SEVEN_OF_DIAMONDS = Diamond, Seven, "7d";
+        SEVEN_OF_CLUBS = Club, Seven, "7c";
+        SEVEN_OF_SPADES = Spade, Seven, "7s";
+        EIGHT_OF_HEARTS = Heart, Eight, "8h";
+        EIGHT_OF_DIAMONDS = Diamond, Eight, "8d";
+        EIGHT_OF_CLUBS = Club, Eight, "8c";
+        EIGHT_OF_SPADES = Spade, Eight, "8s";
+        NINE_OF_HEARTS = Heart, Nine, "9h";
+        NINE_OF_DIAMONDS = Diamond, Nine, "9d";
+        NINE_OF_CLUBS = Club, Nine, "9c";
+        NINE_OF_SPADES = Spade, Nine, "9s";
+        TEN_OF_HEARTS = Heart, Ten, "10h";
+        TEN_OF_DIAMONDS = Diamond, Ten, "10d";
+        TEN_OF_CLUBS = Club, Ten, "10c";
+        TEN_OF_SPADES = Spade, Ten, "10s";
+        JACK_OF_HEARTS = Heart, Jack, "Jh";
+        JACK_OF_DIAMONDS = Diamond, Jack, "Jd";
+        JACK_OF_CLUBS = Club, Jack, "Jc";
+        JACK_OF_SPADES = Spade, Jack, "Js";
+        QUEEN_OF_HEARTS = Heart, Queen, "Qh";
+        QUEEN_OF_DIAMONDS = Diamond, Queen, "Qd";
+        QUEEN_OF_CLUBS = Club, Queen, "Qc";
+        QUEEN_OF_SPADES = Spade, Queen, "Qs";
+        KING_OF_HEARTS = Heart, King, "Kh";
+        KING_OF_DIAMONDS = Diamond, King, "Kd";
+        KING_OF_CLUBS = Club, King, "Kc";
+        KING_OF_SPADES = Spade, King, "Ks";
+        ACE_OF_HEARTS = Heart, Ace, "Ah";
+        ACE_OF_DIAMONDS = Diamond, Ace, "Ad";
+        ACE_OF_CLUBS = Club, Ace, "Ac";
+        ACE_OF_SPADES = Spade, Ace, "As";
+    }
+}
+
+pub fn count_broadway(cards: &[Card]) -> usize {
+    cards.iter().filter(|c| c.is_broadway()).count()
+}
+
+/// How many cards of each value, indexed by `value - 2` as in `to_index`.
+pub fn value_counts(cards: &[Card]) -> [u8; 13] {
+    let mut counts = [0; 13];
+    for card in cards {
+        counts[card.value() as usize - 2] += 1;
+    }
+    counts
+}
+
+/// How many cards of each suit, indexed by `Suit::index`.
+pub fn suit_counts(cards: &[Card]) -> [u8; 4] {
+    let mut counts = [0; 4];
+    for card in cards {
+        counts[card.suit().index() as usize] += 1;
+    }
+    counts
+}
+
+/// ORs the masks of all the cards together.
+pub fn cards_to_mask(cards: &[Card]) -> u64 {
+    cards.iter().fold(0, |mask, c| mask | c.mask())
+}
+
+/// A set of cards backed by a `u64` using the `CardKind::mask` bit layout:
+/// the 52 standard cards in bits `0..52`, then the two jokers.
+///
+/// Jokers are only seen through `iter_kinds`; `iter` and the other
+/// `Card`-typed views skip them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const fn new() -> Self {
+        CardSet(0)
+    }
+
+    /// All 52 standard cards.
+    pub const fn full() -> Self {
+        CardSet((1 << 52) - 1)
+    }
+
+    /// All 52 standard cards and both jokers.
+    pub const fn full_with_jokers() -> Self {
+        CardSet((1 << 54) - 1)
+    }
+
+    /// Bits above the 54 cards are dropped.
+    pub fn from_mask(mask: u64) -> Self {
+        CardSet(mask & CardSet::full_with_jokers().0)
+    }
+
+    pub fn mask(&self) -> u64 {
+        self.0
+    }
+
+    /// Adds the card, returning `false` if it was already present.
+    pub fn insert(&mut self, card: impl Into<CardKind>) -> bool {
+        let mask = card.into().mask();
+        let present = self.0 & mask != 0;
+        self.0 |= mask;
+        !present
+    }
+
+    /// Removes the card, returning `false` if it was not present.
+    pub fn remove(&mut self, card: impl Into<CardKind>) -> bool {
+        let mask = card.into().mask();
+        let present = self.0 & mask != 0;
+        self.0 &= !mask;
+        present
+    }
+
+    pub fn contains(&self, card: impl Into<CardKind>) -> bool {
+        self.0 & card.into().mask() != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    /// The standard cards in `to_index` order, lowest card first.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0 & CardSet::full().0)
+    }
+
+    /// Every card including jokers, in `CardKind::to_index` order.
+    pub fn iter_kinds(&self) -> impl Iterator<Item = CardKind> + '_ {
+        (0..54)
+            .filter(|i| self.0 & 1 << i != 0)
+            .map(|i| CardKind::from_index(i).unwrap())
+    }
+}
+
+impl std::ops::BitOr for CardSet {
+    type Output = CardSet;
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        self.union(&rhs)
+    }
+}
+
+impl std::ops::BitAnd for CardSet {
+    type Output = CardSet;
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        self.intersection(&rhs)
+    }
+}
+
+impl std::ops::Sub for CardSet {
+    type Output = CardSet;
+    fn sub(self, rhs: CardSet) -> CardSet {
+        self.difference(&rhs)
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|c| {
+            self.insert(c);
+        });
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Card::from_index(index).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+/// Returns the first card that appears more than once.
+pub fn find_duplicate(cards: &[Card]) -> Option<Card> {
+    let mut seen = HashSet::with_capacity(cards.len());
+    cards.iter().find(|c| !seen.insert(**c)).copied()
+}
+
+pub fn has_duplicates(cards: &[Card]) -> bool {
+    find_duplicate(cards).is_some()
+}
+
+/// Parses every whitespace/comma separated token, keeping the cards that
+/// parse and collecting each failure with its token index.
+///
+/// A token may hold several run-together cards like `AsKd`; if any of them
+/// is bad the whole token is reported.
+pub fn parse_cards(input: &str) -> (Vec<Card>, Vec<(usize, Error)>) {
+    let mut cards = Vec::new();
+    let mut errors = Vec::new();
+    let tokens = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty());
+    for (i, token) in tokens.enumerate() {
+        match Card::parse_many(token) {
+            Ok(parsed) => cards.extend(parsed),
+            Err(e) => errors.push((i, e)),
+        }
+    }
+    (cards, errors)
+}
+
+/// Parsing methods on strings, e.g. `"As Kd".parse_cards()`.
+pub trait ParseCardExt {
+    /// Same as `Card::try_from`.
+    fn parse_card(&self) -> Result<Card, Error>;
+    /// Same as `Card::parse_many`.
+    fn parse_cards(&self) -> Result<Vec<Card>, Error>;
+    /// Same as `HoldemHand::try_from`.
+    fn parse_hand(&self) -> Result<HoldemHand, Error>;
+}
+
+impl ParseCardExt for str {
+    fn parse_card(&self) -> Result<Card, Error> {
+        Card::try_from(self)
+    }
+
+    fn parse_cards(&self) -> Result<Vec<Card>, Error> {
+        Card::parse_many(self)
+    }
+
+    fn parse_hand(&self) -> Result<HoldemHand, Error> {
+        HoldemHand::try_from(self)
+    }
+}
+
+/// Any card that can come out of a pack, jokers included.
+///
+/// Jokers order above every standard card and use `Joker`'s `sj` / `bj`
+/// string form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CardKind {
+    Standard(Card),
+    Joker(Joker),
+}
+
+impl CardKind {
+    pub fn is_joker(&self) -> bool {
+        matches!(self, CardKind::Joker(_))
+    }
+
+    pub fn card(&self) -> Option<Card> {
+        match self {
+            CardKind::Standard(card) => Some(*card),
+            CardKind::Joker(_) => None,
+        }
+    }
+
+    /// `Card::to_index` for standard cards, then 52 for the small joker
+    /// and 53 for the big joker.
+    pub fn to_index(&self) -> u8 {
+        match self {
+            CardKind::Standard(card) => card.to_index(),
+            CardKind::Joker(Joker::Small) => 52,
+            CardKind::Joker(Joker::Big) => 53,
+        }
+    }
+
+    pub fn from_index(index: u8) -> Result<CardKind, Error> {
+        match index {
+            52 => Ok(CardKind::Joker(Joker::Small)),
+            53 => Ok(CardKind::Joker(Joker::Big)),
+            _ => Card::from_index(index).map(CardKind::Standard),
+        }
+    }
+
+    pub fn mask(&self) -> u64 {
+        1 << self.to_index()
+    }
+
+    /// Like `Card::to_unicode_char`; the big joker is the red joker `🂿`
+    /// and the small joker the black joker `🃏`.
+    pub fn to_unicode_char(&self) -> char {
+        match self {
+            CardKind::Standard(card) => card.to_unicode_char(),
+            CardKind::Joker(Joker::Big) => '\u{1F0BF}',
+            CardKind::Joker(Joker::Small) => '\u{1F0CF}',
+        }
+    }
+
+    pub fn from_unicode_char(c: char) -> Result<CardKind, Error> {
+        match c {
+            '\u{1F0BF}' => Ok(CardKind::Joker(Joker::Big)),
+            '\u{1F0CF}' => Ok(CardKind::Joker(Joker::Small)),
+            _ => Card::from_unicode_char(c).map(CardKind::Standard),
+        }
+    }
+}
+
+impl From<Card> for CardKind {
+    fn from(card: Card) -> Self {
+        CardKind::Standard(card)
+    }
+}
+
+impl From<Joker> for CardKind {
+    fn from(joker: Joker) -> Self {
+        CardKind::Joker(joker)
+    }
+}
+
+impl TryFrom<&str> for CardKind {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match Joker::try_from(value) {
+            Ok(joker) => Ok(CardKind::Joker(joker)),
+            Err(_) => Ok(CardKind::Standard(Card::try_from(value)?)),
+        }
+    }
+}
+
+impl FromStr for CardKind {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl Display for CardKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardKind::Standard(card) => card.fmt(f),
+            CardKind::Joker(joker) => joker.fmt(f),
+        }
+    }
+}
+
+/// `count` deals of `N` distinct cards from a fixed xorshift sequence, so
+/// tests get the same "random" hands on every run.
+#[cfg(test)]
+pub(crate) fn sample_deals<const N: usize>(seed: u64, count: usize) -> Vec<[Card; N]> {
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Card::ALL[(state % 52) as usize]
+    };
+    (0..count)
+        .map(|_| {
+            let mut cards = Vec::with_capacity(N);
+            while cards.len() < N {
+                let card = next();
+                if !cards.contains(&card) {
+                    cards.push(card);
+                }
+            }
+            cards.try_into().unwrap()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suit() {
+        assert_eq!(Suit::try_from("h"), Ok(Suit::Heart));
+        assert_eq!(Suit::try_from("d"), Ok(Suit::Diamond));
+        assert_eq!(Suit::try_from("c"), Ok(Suit::Club));
+        assert_eq!(Suit::try_from("s"), Ok(Suit::Spade));
+        assert_eq!(Suit::try_from("H"), Ok(Suit::Heart));
+        assert_eq!(Suit::try_from("D"), Ok(Suit::Diamond));
+        assert_eq!(Suit::try_from("C"), Ok(Suit::Club));
+        assert_eq!(Suit::try_from("S"), Ok(Suit::Spade));
+        assert_eq!(Suit::try_from("x"), Err(Error::BadSuit("x".to_string())));
+        assert_eq!(Suit::try_from(""), Err(Error::BadSuit("".to_string())));
+    }
+
+    #[test]
+    fn test_card_size() {
+        const _: () = assert!(std::mem::size_of::<Card>() == 1);
+        const _: () = assert!(std::mem::size_of::<Option<Card>>() == 1);
+        assert_eq!(std::mem::size_of::<[Option<Card>; 5]>(), 5);
+        for card in Card::ALL {
+            assert_eq!(Card::new(card.suit(), card.value()), card);
+        }
+    }
+
+    #[test]
+    fn test_suit_index() {
+        for (i, suit) in Suit::ALL.into_iter().enumerate() {
+            assert_eq!(suit.index(), i as u8);
+            assert_eq!(Suit::from_index(i as u8), Ok(suit));
+            assert_eq!(Suit::try_from(i as u8), Ok(suit));
+        }
+        const SPADE: u8 = Suit::Spade.index();
+        assert_eq!(SPADE, 3);
+        assert_eq!(Suit::from_index(4), Err(Error::BadSuitIndex(4)));
+        assert_eq!(Suit::try_from(255u8), Err(Error::BadSuitIndex(255)));
+        const CLUB: Option<Suit> = Suit::checked_from_index(2);
+        assert_eq!(CLUB, Some(Suit::Club));
+        assert_eq!(Suit::checked_from_index(4), None);
+    }
+
+    #[test]
+    fn test_value_index() {
+        for (i, value) in Value::ALL.into_iter().enumerate() {
+            assert_eq!(value.index(), i as u8);
+            assert_eq!(Value::from_index(i as u8), Ok(value));
+        }
+        assert_eq!(Value::from_index(13), Err(Error::BadValueIndex(13)));
+        assert_eq!(Value::from_index(255), Err(Error::BadValueIndex(255)));
+        const KING: Option<Value> = Value::checked_from_index(11);
+        assert_eq!(KING, Some(Value::King));
+        assert_eq!(Value::checked_from_index(13), None);
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!(Value::try_from("a"), Ok(Value::Ace));
+        assert_eq!(Value::try_from("A"), Ok(Value::Ace));
+        assert_eq!(Value::try_from("2"), Ok(Value::Two));
+        assert_eq!(Value::try_from("10"), Ok(Value::Ten));
+        assert_eq!(Value::try_from("t"), Ok(Value::Ten));
+        assert_eq!(Value::try_from("T"), Ok(Value::Ten));
+        assert_eq!(
+            Value::try_from("TT"),
+            Err(Error::BadValue("TT".to_string()))
+        );
+        assert_eq!(
+            Value::try_from("13"),
+            Err(Error::BadValue("13".to_string()))
+        );
+        assert_eq!(Value::try_from("0"), Err(Error::BadValue("0".to_string())));
+        assert_eq!(Value::try_from("1"), Err(Error::BadValue("1".to_string())));
+
+        // eq
+        assert_ne!(Value::Ace, Value::Two);
+        assert_eq!(Value::Ace, Value::Ace);
+        assert_eq!(Value::Ace, 14);
+        assert_eq!(Value::King, 13);
+        assert_ne!(Value::Ace, 1);
+        assert_ne!(1, Value::Ace);
+        assert_eq!(14, Value::Ace);
+
+        // ord
+        assert!(Value::Two < Value::Ace);
+
+        // Add
+        assert_eq!(Value::Ace + 1, 15);
+        assert_eq!(1 + Value::Ten, 11);
+
+        // Sub
+        assert_eq!(Value::Ace - 1, 13);
+        assert_eq!(Value::Two - 2, 0);
+        assert_eq!(20 - Value::Ten, 10);
+
+        // checked rank arithmetic
+        assert_eq!(Value::Two.checked_sub_rank(1), None);
+        assert_eq!(Value::King.checked_add_rank(1), Some(Value::Ace));
+        assert_eq!(Value::Ace.checked_add_rank(1), None);
+        assert_eq!(Value::Ace.checked_add_rank(255), None);
+        assert_eq!(Value::Six.checked_sub_rank(4), Some(Value::Two));
+        assert_eq!(Value::Six.checked_sub_rank(5), None);
+        assert_eq!(Value::Six.checked_sub_rank(200), None);
+        assert_eq!(Value::Nine.checked_add_rank(0), Some(Value::Nine));
+
+        // TryFrom<u8>
+        assert_eq!(Value::try_from(14u8), Ok(Value::Ace));
+        assert_eq!(Value::try_from(2u8), Ok(Value::Two));
+        assert_eq!(Value::try_from(13u8), Ok(Value::King));
+        assert_eq!(Value::try_from(1u8), Err(Error::BadValue("1".to_string())));
+        assert_eq!(Value::try_from(0u8), Err(Error::BadValue("0".to_string())));
+        assert_eq!(
+            Value::try_from(15u8),
+            Err(Error::BadValue("15".to_string()))
+        );
+        for v in Value::ALL {
+            assert_eq!(Value::try_from(v.value()), Ok(v));
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_all_consts() {
+        assert_eq!(Value::ALL[0], Value::Two);
+        assert_eq!(Value::ALL[12], Value::Ace);
+        assert_eq!(Value::ALL.len(), Value::COUNT);
+        assert_eq!(Suit::ALL.len(), Suit::COUNT);
+        assert!(Value::ALL.windows(2).all(|w| w[0] < w[1]));
+        for (i, suit) in Suit::ALL.into_iter().enumerate() {
+            assert_eq!(suit as usize, i);
+        }
+        for (i, value) in Value::ALL.into_iter().enumerate() {
+            assert_eq!(value.value() as usize, i + 2);
+            assert_eq!(value.to_string().parse::<Value>(), Ok(value));
+        }
+        let mut old = Value::values();
+        old.sort();
+        assert_eq!(old, Value::ALL);
+        assert_eq!(Suit::values(), Suit::ALL);
+    }
+
+    #[test]
+    fn test_next_prev() {
+        use Value::*;
+        assert_eq!(Ace.next(), None);
+        assert_eq!(Two.prev(), None);
+        assert_eq!(King.next(), Some(Ace));
+        assert_eq!(Ace.prev(), Some(King));
+        assert_eq!(Ace.next_wrapping(), Two);
+        assert_eq!(Two.prev_wrapping(), Ace);
+        assert_eq!(Five.next_wrapping(), Six);
+        assert_eq!(Five.prev_wrapping(), Four);
+
+        let mut walk = vec![Two];
+        while let Some(v) = walk.last().unwrap().next() {
+            walk.push(v);
+        }
+        assert_eq!(walk.len(), 13);
+        assert_eq!(walk.last(), Some(&Ace));
+        assert_eq!(walk, Value::ALL);
+
+        let mut back = vec![Ace];
+        while let Some(v) = back.last().unwrap().prev() {
+            back.push(v);
+        }
+        back.reverse();
+        assert_eq!(back, walk);
+    }
+
+    #[test]
+    fn test_range() {
+        use Value::*;
+        assert_eq!(Value::range(Two, Ace).count(), 13);
+        assert_eq!(Value::range(Two, Ace).len(), 13);
+        assert_eq!(
+            Value::range(Six, Ten).collect::<Vec<_>>(),
+            [Six, Seven, Eight, Nine, Ten]
+        );
+        assert_eq!(
+            Value::range_exclusive(Six, Ten).collect::<Vec<_>>(),
+            [Six, Seven, Eight, Nine]
+        );
+        assert_eq!(
+            Value::range(Jack, Ace).rev().collect::<Vec<_>>(),
+            [Ace, King, Queen, Jack]
+        );
+        assert_eq!(Value::range(Five, Five).collect::<Vec<_>>(), [Five]);
+        assert_eq!(Value::range_exclusive(Five, Five).count(), 0);
+        assert_eq!(Value::range(Ace, Two).count(), 0);
+        assert_eq!(Value::range(Ace, Two).next_back(), None);
+        assert_eq!(Value::range_exclusive(King, Two).len(), 0);
+
+        assert_eq!(Suit::iter().count(), 4);
+        assert_eq!(Suit::iter().next(), Some(Suit::Heart));
+    }
+
+    #[test]
+    fn test_predicates() {
+        use Value::*;
+        let members = |f: fn(Value) -> bool| -> Vec<Value> {
+            Value::ALL.into_iter().filter(|v| f(*v)).collect()
+        };
+        assert_eq!(members(Value::is_face), [Jack, Queen, King]);
+        assert_eq!(members(Value::is_broadway), [Ten, Jack, Queen, King, Ace]);
+        assert_eq!(members(Value::is_wheel_card), [Two, Three, Four, Five, Ace]);
+        assert!(Ace.is_broadway() && Ace.is_wheel_card() && !Ace.is_face());
+        assert!(Ten.is_broadway() && !Ten.is_face());
+
+        let cards = Card::parse_many("As Kd 10h 9c 5s 2d").unwrap();
+        assert_eq!(count_broadway(&cards), 3);
+        assert_eq!(cards.iter().filter(|c| c.is_face()).count(), 1);
+        assert_eq!(cards.iter().filter(|c| c.is_wheel_card()).count(), 3);
+        assert!(cards[0].is_broadway());
+    }
+
+    #[test]
+    fn test_gap() {
+        use Value::*;
+        assert_eq!(Ace.gap(King), 1);
+        assert_eq!(Ace.gap(Two), 12);
+        assert_eq!(Ace.min_gap(Two), 1);
+        assert_eq!(Seven.gap(Two), 5);
+        assert_eq!(Two.gap(Seven), 5);
+        assert_eq!(Seven.min_gap(Two), 5);
+        assert_eq!(Ace.min_gap(Five), 4);
+        assert_eq!(Ace.min_gap(Ace), 0);
+        assert_eq!(Nine.min_gap(Ten), 1);
+
+        let card = |s| Card::try_from(s).unwrap();
+        assert!(card("As").is_connected(&card("Kd")));
+        assert!(card("As").is_connected(&card("2d")));
+        assert!(card("9c").is_connected(&card("10c")));
+        assert!(!card("9c").is_connected(&card("Jc")));
+        assert!(!card("9c").is_connected(&card("9d")));
+    }
+
+    #[test]
+    fn test_low_value() {
+        use Value::*;
+        assert!(LowValue(Ace) < LowValue(Two));
+        assert!(LowValue(King) > LowValue(Queen));
+        assert_eq!(LowValue(Ace), 1);
+        assert_eq!(1, LowValue(Ace));
+        assert_ne!(LowValue(Ace), 14);
+        assert_eq!(LowValue(Ten), 10);
+        assert_eq!(Ace.low_value(), 1);
+        assert_eq!(Value::from(LowValue::from(Ace)), Ace);
+
+        let mut cards: Vec<Card> = ["3h", "Ac", "5d", "2s", "4h"]
+            .into_iter()
+            .map(|s| Card::try_from(s).unwrap())
+            .collect();
+        cards.sort_by_key(|c| std::cmp::Reverse(LowValue(c.value())));
+        let values: Vec<Value> = cards.iter().map(|c| c.value()).collect();
+        assert_eq!(values, [Five, Four, Three, Two, Ace]);
     }
 
     #[test]
     #[rustfmt::skip]
     fn test_card() {
-        assert_eq!(Card::try_from("2h"), Ok(Card(Suit::Heart, Value::Two)));
-        assert_eq!(Card::try_from("2H"), Ok(Card(Suit::Heart, Value::Two)));
-        assert_eq!(Card::try_from("Ad"), Ok(Card(Suit::Diamond, Value::Ace)));
-        assert_eq!(Card::try_from("aD"), Ok(Card(Suit::Diamond, Value::Ace)));
-        assert_eq!(Card::try_from("10d"), Ok(Card(Suit::Diamond, Value::Ten)));
+        assert_eq!(Card::try_from("2h"), Ok(Card::new(Suit::Heart, Value::Two)));
+        assert_eq!(Card::try_from("2H"), Ok(Card::new(Suit::Heart, Value::Two)));
+        assert_eq!(Card::try_from("Ad"), Ok(Card::new(Suit::Diamond, Value::Ace)));
+        assert_eq!(Card::try_from("aD"), Ok(Card::new(Suit::Diamond, Value::Ace)));
+        assert_eq!(Card::try_from("10d"), Ok(Card::new(Suit::Diamond, Value::Ten)));
 
         // bad suit to parse
         assert_eq!(Card::try_from("Ak"), Err(Error::BadSuit("k".to_string())));
@@ -261,4 +1835,706 @@ mod tests {
         assert_eq!(Card::try_from("*"), Err(Error::BadCard("invalid length".to_string())));
         assert_eq!(Card::try_from(""), Err(Error::BadCard("invalid length".to_string())));
     }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_ten_alias() {
+        assert_eq!(Card::try_from("Td"), Card::try_from("10d"));
+        assert_eq!(Card::try_from("th"), Ok(Card::new(Suit::Heart, Value::Ten)));
+        assert_eq!(Card::try_from("TT"), Err(Error::BadSuit("T".to_string())));
+        assert_eq!(Card::try_from("T"), Err(Error::BadCard("invalid length".to_string())));
+
+        assert_eq!(Card::new(Suit::Spade, Value::Ten).to_compact_string(), "Ts");
+        assert_eq!(Card::new(Suit::Spade, Value::Ten).to_string(), "10s");
+        assert_eq!(Card::new(Suit::Heart, Value::Ace).to_compact_string(), "Ah");
+        assert_eq!(Card::new(Suit::Club, Value::Nine).to_compact_string(), "9c");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_suit_symbols() {
+        assert_eq!(Suit::try_from("♠"), Ok(Suit::Spade));
+        assert_eq!(Suit::try_from("♥"), Ok(Suit::Heart));
+        assert_eq!(Card::try_from("A♠"), Ok(Card::new(Suit::Spade, Value::Ace)));
+        assert_eq!(Card::try_from("K♦"), Ok(Card::new(Suit::Diamond, Value::King)));
+        assert_eq!(Card::try_from("10♥"), Ok(Card::new(Suit::Heart, Value::Ten)));
+        assert_eq!(Card::try_from("T♣"), Ok(Card::new(Suit::Club, Value::Ten)));
+
+        assert_eq!(Card::try_from("A☃"), Err(Error::BadSuit("☃".to_string())));
+        assert_eq!(Card::try_from("Aé"), Err(Error::BadSuit("é".to_string())));
+        assert_eq!(Card::try_from("♠A"), Err(Error::BadSuit("A".to_string())));
+        assert_eq!(Card::try_from("100♠"), Err(Error::BadCard("invalid length".to_string())));
+
+        assert_eq!(format!("{:#}", Suit::Spade), "♠");
+        assert_eq!(format!("{:#}", Card::new(Suit::Spade, Value::Ace)), "A♠");
+        assert_eq!(format!("{:#}", Card::new(Suit::Heart, Value::Ten)), "10♥");
+        assert_eq!(format!("{}", Card::new(Suit::Heart, Value::Ten)), "10h");
+    }
+
+    #[test]
+    fn test_byte() {
+        for card in Card::all() {
+            assert_eq!(Card::from_byte(card.to_byte()), Ok(card));
+        }
+        assert_eq!(consts::TWO_OF_HEARTS.to_byte(), 8);
+        assert_eq!(consts::ACE_OF_SPADES.to_byte(), 59);
+        for byte in (0..8).chain(60..=255) {
+            assert_eq!(
+                Card::from_byte(byte),
+                Err(Error::BadCard(format!("invalid byte {}", byte)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut all = 0u64;
+        for card in Card::all() {
+            let mask = card.mask();
+            assert_eq!(mask.count_ones(), 1);
+            assert_eq!(all & mask, 0);
+            all |= mask;
+            assert_eq!(Card::from_mask(mask), Ok(card));
+        }
+        assert_eq!(all.count_ones(), 52);
+        assert_eq!(cards_to_mask(&Card::ALL), all);
+        assert_eq!(all, (1 << 52) - 1);
+        assert_eq!(consts::TWO_OF_HEARTS.mask(), 1);
+        assert_eq!(consts::ACE_OF_SPADES.mask(), 1 << 51);
+        assert_eq!(cards_to_mask(&[]), 0);
+
+        assert_eq!(
+            Card::from_mask(0),
+            Err(Error::BadCard("invalid mask 0x0".to_string()))
+        );
+        assert_eq!(
+            Card::from_mask(0b11),
+            Err(Error::BadCard("invalid mask 0x3".to_string()))
+        );
+        assert!(Card::from_mask(1 << 52).is_err());
+        assert!(Card::from_mask(1 << 63).is_err());
+    }
+
+    #[test]
+    fn test_card_set() {
+        let deck: CardSet = Card::all().collect();
+        assert_eq!(deck, CardSet::full());
+        assert_eq!(deck.len(), 52);
+        assert!(!deck.is_empty());
+        assert!(CardSet::new().is_empty());
+        assert_eq!(deck.iter().collect::<Vec<_>>(), Card::ALL);
+
+        let board: CardSet = Card::parse_many("As Kd 7c 7h 2s")
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(board.len(), 5);
+        let rest = deck - board;
+        assert_eq!(rest.len(), 47);
+        assert!(!rest.contains(consts::ACE_OF_SPADES));
+        assert!(rest.contains(consts::ACE_OF_HEARTS));
+        assert_eq!(rest | board, deck);
+        assert_eq!(rest & board, CardSet::new());
+        assert_eq!(deck & board, board);
+        assert_eq!(rest.union(&board), deck);
+        assert_eq!(deck.difference(&rest), board);
+        assert_eq!(deck.intersection(&board), board);
+
+        let ordered: Vec<String> = board.iter().map(|c| c.to_string()).collect();
+        assert_eq!(ordered, ["2s", "7h", "7c", "Kd", "As"]);
+        assert_eq!(board.iter().len(), 5);
+
+        let mut set = CardSet::new();
+        assert!(set.insert(consts::TEN_OF_CLUBS));
+        assert!(!set.insert(consts::TEN_OF_CLUBS));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove(consts::TEN_OF_CLUBS));
+        assert!(!set.remove(consts::TEN_OF_CLUBS));
+        assert!(set.is_empty());
+        assert_eq!(CardSet::from_mask(board.mask()), board);
+        assert_eq!(CardSet::from_mask(u64::MAX), CardSet::full_with_jokers());
+    }
+
+    #[test]
+    fn test_card_set_jokers() {
+        let mut set = CardSet::full_with_jokers();
+        assert_eq!(set.len(), 54);
+        assert_eq!(set.iter().len(), 52);
+        assert_eq!(set.iter_kinds().count(), 54);
+        assert!(set.contains(Joker::Big));
+        assert!(set.remove(Joker::Small));
+        assert!(set.remove(Joker::Big));
+        assert!(!set.remove(Joker::Big));
+        assert_eq!(set, CardSet::full());
+        assert!(set.insert(CardKind::Joker(Joker::Small)));
+        assert_eq!(set.iter_kinds().last(), Some(CardKind::Joker(Joker::Small)));
+
+        for i in 0..54 {
+            assert_eq!(CardKind::from_index(i).map(|c| c.to_index()), Ok(i));
+        }
+        assert_eq!(CardKind::from_index(52), Ok(CardKind::Joker(Joker::Small)));
+        assert_eq!(CardKind::from_index(53), Ok(CardKind::Joker(Joker::Big)));
+        assert_eq!(
+            CardKind::from_index(54),
+            Err(Error::BadCard("invalid index 54".to_string()))
+        );
+        assert!(Card::from_index(53).is_err());
+        assert_eq!(CardKind::Joker(Joker::Big).mask(), 1 << 53);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut seen = [false; 52];
+        for suit in Suit::ALL {
+            for value in Value::ALL {
+                let card = Card::new(suit, value);
+                let index = card.to_index();
+                assert!(index < 52);
+                assert!(!seen[index as usize]);
+                seen[index as usize] = true;
+                assert_eq!(Card::from_index(index), Ok(card));
+            }
+        }
+        assert_eq!(Card::try_from("2h").unwrap().to_index(), 0);
+        assert_eq!(Card::try_from("2s").unwrap().to_index(), 3);
+        assert_eq!(Card::try_from("3h").unwrap().to_index(), 4);
+        assert_eq!(Card::try_from("As").unwrap().to_index(), 51);
+        assert_eq!(
+            Card::from_index(52),
+            Err(Error::BadCard("invalid index 52".to_string()))
+        );
+        assert!(Card::from_index(u8::MAX).is_err());
+    }
+
+    #[test]
+    fn test_joker() {
+        assert_eq!(Joker::try_from("sj"), Ok(Joker::Small));
+        assert_eq!(Joker::try_from("BJ"), Ok(Joker::Big));
+        assert_eq!(Joker::try_from("jk"), Err(Error::BadCard("jk".to_string())));
+        assert_eq!(Joker::try_from("As"), Err(Error::BadCard("As".to_string())));
+        assert_eq!(Joker::Small.to_string(), "sj");
+        assert_eq!(Joker::Big.to_string(), "bj");
+        for joker in [Joker::Small, Joker::Big] {
+            assert_eq!(joker.to_string().parse::<Joker>(), Ok(joker));
+        }
+        let small: Joker = Joker::Small.to_string().parse().unwrap();
+        let big: Joker = Joker::Big.to_string().parse().unwrap();
+        assert!(big > small);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_card_kind() {
+        let small = CardKind::Joker(Joker::Small);
+        let big = CardKind::Joker(Joker::Big);
+        let ace = CardKind::Standard(Card::new(Suit::Spade, Value::Ace));
+        assert_eq!(CardKind::try_from("sj"), Ok(small));
+        assert_eq!(CardKind::try_from("BJ"), Ok(big));
+        assert_eq!(CardKind::try_from("As"), Ok(ace));
+        assert_eq!(CardKind::try_from("10d"), Ok(Card::new(Suit::Diamond, Value::Ten).into()));
+        assert_eq!(CardKind::try_from("xj"), Err(Error::BadSuit("j".to_string())));
+        assert_eq!(CardKind::try_from("joker"), Err(Error::BadCard("invalid length".to_string())));
+
+        assert!(big > small);
+        assert!(small > ace);
+        assert!(small > CardKind::Standard(Card::new(Suit::Heart, Value::Ace)));
+
+        assert_eq!(small.to_string(), "sj");
+        assert_eq!(big.to_string(), "bj");
+        assert_eq!(ace.to_string(), "As");
+        assert_eq!(format!("{:#}", ace), "A♠");
+        assert!(big.is_joker());
+        assert_eq!(big.card(), None);
+        assert_eq!(ace.card(), Some(Card::new(Suit::Spade, Value::Ace)));
+    }
+
+    #[test]
+    fn test_card_order() {
+        let card = |s| Card::try_from(s).unwrap();
+        assert!(card("Ah") > card("Ks"));
+        assert!(card("2s") < card("Ah"));
+        assert!(card("2s") > card("2h"));
+        assert!(card("10c") < card("Jh"));
+        let mut sorted = Card::ALL;
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, Card::ALL);
+    }
+
+    #[test]
+    fn test_all() {
+        let cards: Vec<Card> = Card::all().collect();
+        assert_eq!(cards.len(), 52);
+        for (i, card) in cards.iter().enumerate() {
+            assert_eq!(card.to_index() as usize, i);
+            assert_eq!(cards.iter().filter(|c| *c == card).count(), 1);
+        }
+        assert_eq!(cards[0], Card::new(Suit::Heart, Value::Two));
+        assert_eq!(cards[51], Card::new(Suit::Spade, Value::Ace));
+        assert_eq!(Card::ALL.to_vec(), cards);
+    }
+
+    #[test]
+    fn test_suit_order() {
+        use Suit::*;
+        let sorted = |order: SuitOrder| {
+            let mut suits = Suit::ALL;
+            suits.sort_by(|a, b| a.cmp_with(*b, order));
+            suits
+        };
+        assert_eq!(sorted(SuitOrder::STANDARD), [Heart, Diamond, Club, Spade]);
+        assert_eq!(sorted(SuitOrder::default()), [Heart, Diamond, Club, Spade]);
+        assert_eq!(sorted(SuitOrder::BRIDGE), [Club, Diamond, Heart, Spade]);
+        assert_eq!(sorted(SuitOrder::BIG_TWO), [Diamond, Club, Heart, Spade]);
+        let custom = SuitOrder::custom([Spade, Heart, Club, Diamond]).unwrap();
+        assert_eq!(sorted(custom), [Spade, Heart, Club, Diamond]);
+        assert!(SuitOrder::custom([Spade, Spade, Club, Diamond]).is_err());
+
+        // the derived ordering is untouched
+        assert!(Heart < Diamond && Diamond < Club && Club < Spade);
+        for a in Suit::ALL {
+            for b in Suit::ALL {
+                assert_eq!(a.cmp_with(b, SuitOrder::STANDARD), a.cmp(&b));
+            }
+        }
+
+        let card = |s| Card::try_from(s).unwrap();
+        let big_two = SuitOrder::BIG_TWO;
+        assert!(card("3h").cmp_with(&card("3c"), big_two).is_gt());
+        assert!(card("3h")
+            .cmp_with(&card("3c"), SuitOrder::STANDARD)
+            .is_lt());
+        assert!(card("3s").cmp_with(&card("4d"), big_two).is_lt());
+        assert!(card("Kd").cmp_with(&card("Kd"), big_two).is_eq());
+    }
+
+    #[test]
+    fn test_color() {
+        assert_eq!(Suit::Heart.color(), Color::Red);
+        assert_eq!(Suit::Diamond.color(), Color::Red);
+        assert_eq!(Suit::Club.color(), Color::Black);
+        assert_eq!(Suit::Spade.color(), Color::Black);
+        assert!(Card::try_from("Ah").unwrap().is_red());
+        assert!(Card::try_from("Kd").unwrap().is_red());
+        assert!(Card::try_from("2c").unwrap().is_black());
+        assert!(!Card::try_from("2s").unwrap().is_red());
+
+        let (red, black) = filter_color(&Card::ALL, Color::Red);
+        assert_eq!(red.len(), 26);
+        assert_eq!(black.len(), 26);
+        assert!(red.iter().all(Card::is_red));
+        assert!(black.iter().all(Card::is_black));
+        let (black, red) = filter_color(&Card::ALL, Color::Black);
+        assert_eq!((black.len(), red.len()), (26, 26));
+    }
+
+    #[test]
+    fn test_consts() {
+        const C: Card = Card::new(Suit::Spade, Value::Ace);
+        const S: Suit = C.suit();
+        const V: Value = C.value();
+        assert_eq!(C, consts::ACE_OF_SPADES);
+        assert_eq!((S, V), (Suit::Spade, Value::Ace));
+        assert_eq!(consts::TEN_OF_HEARTS, Card::try_from("10h").unwrap());
+
+        for (card, s) in consts::TABLE {
+            assert_eq!(Card::try_from(s), Ok(card));
+        }
+        let table: Vec<Card> = consts::TABLE.iter().map(|(c, _)| *c).collect();
+        assert_eq!(table, Card::ALL);
+    }
+
+    #[test]
+    fn test_macros() {
+        assert_eq!(card!("As"), consts::ACE_OF_SPADES);
+        assert_eq!(card!("10h"), consts::TEN_OF_HEARTS);
+        assert_eq!(card!("Th"), consts::TEN_OF_HEARTS);
+        assert_eq!(card!("kD"), consts::KING_OF_DIAMONDS);
+        assert_eq!(
+            cards!("As", "Kd", "2c"),
+            [
+                consts::ACE_OF_SPADES,
+                consts::KING_OF_DIAMONDS,
+                consts::TWO_OF_CLUBS
+            ]
+        );
+        for (card, s) in consts::TABLE {
+            assert_eq!(Card::__from_literal(s), Some(card));
+        }
+        for bad in ["", "A", "Ax", "1s", "100s", "11s", "A♠", "10"] {
+            assert_eq!(Card::__from_literal(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let set: HashSet<Card> = Card::all().collect();
+        assert_eq!(set.len(), 52);
+        assert!(!has_duplicates(&Card::ALL));
+
+        let cards: Vec<Card> = ["2c", "3d", "As", "3d", "2c"]
+            .into_iter()
+            .map(|s| Card::try_from(s).unwrap())
+            .collect();
+        assert!(has_duplicates(&cards));
+        assert_eq!(
+            find_duplicate(&cards),
+            Some(Card::new(Suit::Diamond, Value::Three))
+        );
+        assert_eq!(find_duplicate(&cards[..3]), None);
+        assert!(!has_duplicates(&[]));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_parse_many() {
+        let parse = |s: &str| Card::parse_many(s).map(|cards| {
+            cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ")
+        });
+        assert_eq!(parse("AsKd2c"), Ok("As Kd 2c".to_string()));
+        assert_eq!(parse("As,Kd 2c"), Ok("As Kd 2c".to_string()));
+        assert_eq!(parse(" As , Kd,,2c "), Ok("As Kd 2c".to_string()));
+        assert_eq!(parse("10h9c10s"), Ok("10h 9c 10s".to_string()));
+        assert_eq!(parse("Th10dA♠"), Ok("10h 10d As".to_string()));
+        assert_eq!(parse(""), Ok("".to_string()));
+
+        assert_eq!(parse("AsKdQx"), Err(Error::BadSuit("x".to_string())));
+        assert_eq!(parse("AsKd1s"), Err(Error::BadValue("1".to_string())));
+        assert_eq!(parse("AsKdQ"), Err(Error::BadCard("incomplete card: Q".to_string())));
+        assert_eq!(parse("AsKd10"), Err(Error::BadCard("incomplete card: 10".to_string())));
+        assert_eq!(parse("AsK Qd"), Err(Error::BadCard("incomplete card: K".to_string())));
+    }
+
+    #[test]
+    fn test_counts() {
+        let cards = Card::parse_many("9h 9d 9s Ah 5h 2h Kc").unwrap();
+        assert_eq!(
+            value_counts(&cards),
+            [1, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 1, 1]
+        );
+        assert_eq!(suit_counts(&cards), [4, 1, 1, 1]);
+        assert_eq!(value_counts(&[]), [0; 13]);
+
+        assert!(cards[0].same_value(&cards[1]));
+        assert!(!cards[0].same_suit(&cards[1]));
+        assert!(cards[0].same_suit(&cards[3]));
+        assert!(!cards[0].same_value(&cards[3]));
+    }
+
+    #[test]
+    fn test_parse_numeric() {
+        use Value::*;
+        let parse = Value::parse_numeric;
+        for mode in [AceMode::Low, AceMode::High, AceMode::Both] {
+            assert_eq!(parse("11", mode), Ok(Jack));
+            assert_eq!(parse("12", mode), Ok(Queen));
+            assert_eq!(parse("13", mode), Ok(King));
+            assert_eq!(parse("10", mode), Ok(Ten));
+            assert_eq!(parse("2", mode), Ok(Two));
+            assert_eq!(parse("A", mode), Ok(Ace));
+            assert_eq!(parse("15", mode), Err(Error::BadValue("15".to_string())));
+        }
+        assert_eq!(parse("1", AceMode::Low), Ok(Ace));
+        assert_eq!(
+            parse("14", AceMode::Low),
+            Err(Error::BadValue("14".to_string()))
+        );
+        assert_eq!(parse("14", AceMode::High), Ok(Ace));
+        assert_eq!(
+            parse("1", AceMode::High),
+            Err(Error::BadValue("1".to_string()))
+        );
+        assert_eq!(parse("1", AceMode::Both), Ok(Ace));
+        assert_eq!(parse("14", AceMode::Both), Ok(Ace));
+
+        // the default parsers stay strict
+        assert!(Value::try_from("1").is_err());
+        assert!(Value::try_from("13").is_err());
+        assert!(Card::try_from("1h").is_err());
+        assert!(Card::try_from("13h").is_err());
+    }
+
+    #[test]
+    fn test_display_padding() {
+        let ten = Card::try_from("10h").unwrap();
+        assert_eq!(format!("{:>4}", ten), " 10h");
+        assert_eq!(format!("{:<5}|", ten), "10h  |");
+        assert_eq!(format!("{:*^7}", ten), "**10h**");
+        assert_eq!(format!("{:>3}", ten), "10h");
+        assert_eq!(format!("{:>#3}", consts::ACE_OF_SPADES), " A♠");
+        assert_eq!(format!("{:-<3}", Suit::Club), "c--");
+        assert_eq!(format!("{:>3}", Value::Ten), " 10");
+        assert_eq!(format!("{:^4}", Value::King), " K  ");
+        assert_eq!(format!("{:>3}", Joker::Big), " bj");
+        assert_eq!(format!("{:>4}", CardKind::Standard(ten)), " 10h");
+    }
+
+    #[test]
+    fn test_card_value_cmp() {
+        let ace = Card::try_from("As").unwrap();
+        let other_ace = Card::try_from("Ah").unwrap();
+        let two = Card::try_from("2h").unwrap();
+        assert!(ace == Value::Ace);
+        assert!(other_ace == Value::Ace);
+        assert!(Value::Ace == ace);
+        assert_ne!(ace, other_ace);
+        assert!(two < Value::Ten);
+        assert!(ace >= Value::Ten);
+        assert!(Value::Ten > two);
+        assert!(ace > other_ace);
+    }
+
+    #[test]
+    fn test_tuple_conversions() {
+        let card: Card = (Suit::Spade, Value::Ace).into();
+        assert_eq!(card, consts::ACE_OF_SPADES);
+        assert_eq!(Card::from((Value::Ace, Suit::Spade)), card);
+
+        let (suit, value) = card.into();
+        assert_eq!((suit, value), (Suit::Spade, Value::Ace));
+        let (value, suit): (Value, Suit) = card.into();
+        assert_eq!(Card::from((value, suit)), card);
+
+        let cards: Vec<Card> = Suit::ALL
+            .into_iter()
+            .zip(Value::ALL)
+            .map(Card::from)
+            .collect();
+        assert_eq!(cards.len(), 4);
+        assert_eq!(cards[3], Card::new(Suit::Spade, Value::Five));
+    }
+
+    #[test]
+    fn test_parse_card_ext() {
+        assert_eq!("As".parse_card(), Ok(consts::ACE_OF_SPADES));
+        assert_eq!(
+            String::from("As Kd").parse_cards(),
+            Ok(vec![consts::ACE_OF_SPADES, consts::KING_OF_DIAMONDS])
+        );
+        assert_eq!("Ax".parse_card(), Err(Error::BadSuit("x".to_string())));
+        assert_eq!(
+            String::from("As 1d").parse_cards(),
+            Err(Error::BadValue("1".to_string()))
+        );
+
+        let hand = "As Ks Qs Js 10s".parse_hand().unwrap();
+        let sorted = String::from("10s Js Qs Ks As").parse_hand().unwrap();
+        assert_eq!(sorted.cards(), hand.cards());
+        assert_eq!(
+            "As Ks".parse_hand(),
+            Err(Error::BadCard("invalid number of cards".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let (cards, errors) = parse_cards("As xx Kd 1h Qc");
+        assert_eq!(cards, cards!["As", "Kd", "Qc"]);
+        assert_eq!(
+            errors,
+            vec![
+                (1, Error::BadSuit("x".to_string())),
+                (3, Error::BadValue("1".to_string())),
+            ]
+        );
+
+        let (cards, errors) = parse_cards(" AsKd, 2c,,");
+        assert_eq!(cards.len(), 3);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_format_with() {
+        let cards: Vec<Card> = ["As", "10h", "Kd", "Jc", "2s"]
+            .into_iter()
+            .map(|s| Card::try_from(s).unwrap())
+            .collect();
+        let format = |style| {
+            cards.iter().map(|c| c.format_with(style)).collect::<Vec<_>>().join(" ")
+        };
+        assert_eq!(format(CardStyle::Standard), "As 10h Kd Jc 2s");
+        assert_eq!(format(CardStyle::Compact), "As Th Kd Jc 2s");
+        assert_eq!(format(CardStyle::Uppercase), "AS 10H KD JC 2S");
+        assert_eq!(format(CardStyle::Symbol), "A♠ 10♥ K♦ J♣ 2♠");
+        assert_eq!(CardStyle::default(), CardStyle::Standard);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_parse_verbose() {
+        assert_eq!(Card::parse_verbose("Ten of Hearts"), Ok(consts::TEN_OF_HEARTS));
+        assert_eq!(Card::parse_verbose("QUEEN CLUBS"), Ok(consts::QUEEN_OF_CLUBS));
+        assert_eq!(Card::parse_verbose("ace of spades"), Ok(consts::ACE_OF_SPADES));
+        assert_eq!(Card::parse_verbose("  two   OF diamond "), Ok(consts::TWO_OF_DIAMONDS));
+
+        assert_eq!(Card::parse_verbose("eleven of cups"), Err(Error::BadValue("eleven".to_string())));
+        assert_eq!(Card::parse_verbose("ten of cups"), Err(Error::BadSuit("cups".to_string())));
+        assert_eq!(Card::parse_verbose("As"), Err(Error::BadCard("As".to_string())));
+        assert_eq!(Card::parse_verbose("ace off spades"), Err(Error::BadCard("ace off spades".to_string())));
+        assert_eq!(Card::try_from("ace of spades"), Err(Error::BadCard("invalid length".to_string())));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_try_from_char() {
+        assert_eq!(Suit::try_from('h'), Ok(Suit::Heart));
+        assert_eq!(Suit::try_from('S'), Ok(Suit::Spade));
+        assert_eq!(Suit::try_from('♦'), Ok(Suit::Diamond));
+        assert_eq!(Suit::try_from('x'), Err(Error::BadSuit("x".to_string())));
+        assert_eq!(Suit::try_from('é'), Err(Error::BadSuit("é".to_string())));
+
+        assert_eq!(Value::try_from('2'), Ok(Value::Two));
+        assert_eq!(Value::try_from('9'), Ok(Value::Nine));
+        assert_eq!(Value::try_from('t'), Ok(Value::Ten));
+        assert_eq!(Value::try_from('T'), Ok(Value::Ten));
+        assert_eq!(Value::try_from('Q'), Ok(Value::Queen));
+        assert_eq!(Value::try_from('a'), Ok(Value::Ace));
+        assert_eq!(Value::try_from('1'), Err(Error::BadValue("1".to_string())));
+        assert_eq!(Value::try_from('0'), Err(Error::BadValue("0".to_string())));
+        assert_eq!(Value::try_from('x'), Err(Error::BadValue("x".to_string())));
+
+        assert_eq!(Card::try_from(('A', 's')), Ok(consts::ACE_OF_SPADES));
+        assert_eq!(Card::try_from(('T', 'H')), Ok(consts::TEN_OF_HEARTS));
+        assert_eq!(Card::try_from(('1', 'x')), Err(Error::BadSuit("x".to_string())));
+        assert_eq!(Card::try_from(('1', 'h')), Err(Error::BadValue("1".to_string())));
+    }
+
+    #[test]
+    fn test_to_char() {
+        let codes: String = Value::ALL.iter().map(|v| v.to_char()).collect();
+        assert_eq!(codes, "23456789TJQKA");
+        for value in Value::ALL {
+            assert_eq!(Value::from_char(value.to_char()), Ok(value));
+            assert_eq!(
+                Value::from_char(value.to_char().to_ascii_lowercase()),
+                Ok(value)
+            );
+        }
+        let codes: String = Suit::ALL.iter().map(|s| s.to_char()).collect();
+        assert_eq!(codes, "hdcs");
+        for suit in Suit::ALL {
+            assert_eq!(Suit::from_char(suit.to_char()), Ok(suit));
+        }
+        assert_eq!(Value::from_char('1'), Err(Error::BadValue("1".to_string())));
+        assert_eq!(Value::from_char('0'), Err(Error::BadValue("0".to_string())));
+        assert_eq!(Suit::from_char('1'), Err(Error::BadSuit("1".to_string())));
+    }
+
+    #[test]
+    fn test_unicode_char() {
+        assert_eq!(consts::ACE_OF_SPADES.to_unicode_char(), '🂡');
+        assert_eq!(consts::JACK_OF_SPADES.to_unicode_char(), '🂫');
+        assert_eq!(consts::QUEEN_OF_SPADES.to_unicode_char(), '🂭');
+        assert_eq!(consts::KING_OF_HEARTS.to_unicode_char(), '🂾');
+        assert_eq!(consts::TEN_OF_CLUBS.to_unicode_char(), '🃚');
+        assert_eq!(consts::TWO_OF_DIAMONDS.to_unicode_char(), '🃂');
+        for card in Card::all() {
+            assert_eq!(Card::from_unicode_char(card.to_unicode_char()), Ok(card));
+        }
+
+        // knights
+        for c in ['🂬', '🂼', '🃌', '🃜'] {
+            assert_eq!(
+                Card::from_unicode_char(c),
+                Err(Error::BadCard(c.to_string()))
+            );
+        }
+        // card backs, trumps and anything else
+        for c in ['🂠', '🂿', '🃟', '🃠', 'A'] {
+            assert_eq!(
+                Card::from_unicode_char(c),
+                Err(Error::BadCard(c.to_string()))
+            );
+        }
+
+        let big = CardKind::Joker(Joker::Big);
+        let small = CardKind::Joker(Joker::Small);
+        assert_eq!(CardKind::from_unicode_char(big.to_unicode_char()), Ok(big));
+        assert_eq!(
+            CardKind::from_unicode_char(small.to_unicode_char()),
+            Ok(small)
+        );
+        assert_eq!(
+            CardKind::from_unicode_char('🂡'),
+            Ok(consts::ACE_OF_SPADES.into())
+        );
+    }
+
+    #[test]
+    fn test_parse_many_cards() {
+        let input: Vec<String> = (0..1000)
+            .flat_map(|_| Card::all().map(|c| c.to_string()))
+            .collect();
+        let mut parsed = 0;
+        for (i, s) in input.iter().enumerate() {
+            assert_eq!(Card::try_from(s.as_str()), Ok(Card::ALL[i % 52]));
+            assert_eq!(
+                Card::try_from(s.to_uppercase().as_str()),
+                Ok(Card::ALL[i % 52])
+            );
+            parsed += 1;
+        }
+        assert_eq!(parsed, 52_000);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_try_from_bytes() {
+        assert_eq!(Card::try_from_bytes(b"As"), Ok(consts::ACE_OF_SPADES));
+        assert_eq!(Card::try_from_bytes(b"aS"), Ok(consts::ACE_OF_SPADES));
+        assert_eq!(Card::try_from_bytes(b"10h"), Ok(consts::TEN_OF_HEARTS));
+        assert_eq!(Card::try_from_bytes(b"Th"), Ok(consts::TEN_OF_HEARTS));
+        assert_eq!(Suit::try_from_bytes(b"D"), Ok(Suit::Diamond));
+        assert_eq!(Value::try_from_bytes(b"10"), Ok(Value::Ten));
+        assert_eq!(Value::try_from_bytes(b"k"), Ok(Value::King));
+
+        assert_eq!(Card::try_from_bytes(b"Ak"), Err(Error::BadSuit("k".to_string())));
+        assert_eq!(Card::try_from_bytes(b"20D"), Err(Error::BadValue("20".to_string())));
+        assert_eq!(Card::try_from_bytes(b"100D"), Err(Error::BadCard("invalid length".to_string())));
+        assert_eq!(Card::try_from_bytes(b""), Err(Error::BadCard("invalid length".to_string())));
+        assert_eq!(Card::try_from_bytes(&[b'A', 0xff]), Err(Error::BadCard("A\u{fffd}".to_string())));
+        assert_eq!(Card::try_from_bytes("A♠".as_bytes()), Err(Error::BadCard("A♠".to_string())));
+        assert_eq!(Suit::try_from_bytes(&[0xff]), Err(Error::BadSuit("\u{fffd}".to_string())));
+        assert_eq!(Value::try_from_bytes(&[0xc3]), Err(Error::BadValue("\u{fffd}".to_string())));
+
+        for card in Card::all() {
+            assert_eq!(Card::try_from_bytes(card.to_string().as_bytes()), Ok(card));
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_from_str() {
+        assert_eq!("h".parse::<Suit>(), Ok(Suit::Heart));
+        assert_eq!("S".parse::<Suit>(), Ok(Suit::Spade));
+        assert_eq!("x".parse::<Suit>(), Err(Error::BadSuit("x".to_string())));
+        assert_eq!("A".parse::<Value>(), Ok(Value::Ace));
+        assert_eq!("10".parse::<Value>(), Ok(Value::Ten));
+        assert_eq!("1".parse::<Value>(), Err(Error::BadValue("1".to_string())));
+        assert_eq!("As".parse::<Card>(), Ok(Card::new(Suit::Spade, Value::Ace)));
+        assert_eq!("10d".parse::<Card>(), Ok(Card::new(Suit::Diamond, Value::Ten)));
+
+        assert_eq!("Ak".parse::<Card>(), Err(Error::BadSuit("k".to_string())));
+        assert_eq!("pk".parse::<Card>(), Err(Error::BadSuit("k".to_string())));
+        assert_eq!("pD".parse::<Card>(), Err(Error::BadValue("p".to_string())));
+        assert_eq!("20D".parse::<Card>(), Err(Error::BadValue("20".to_string())));
+        assert_eq!("0D".parse::<Card>(), Err(Error::BadValue("0".to_string())));
+        assert_eq!("*D".parse::<Card>(), Err(Error::BadValue("*".to_string())));
+        assert_eq!("100D".parse::<Card>(), Err(Error::BadCard("invalid length".to_string())));
+        assert_eq!("*".parse::<Card>(), Err(Error::BadCard("invalid length".to_string())));
+        assert_eq!("".parse::<Card>(), Err(Error::BadCard("invalid length".to_string())));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for suit in Suit::ALL {
+            for value in Value::ALL {
+                let card = Card::new(suit, value);
+                assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+                assert_eq!(suit.to_string().parse::<Suit>(), Ok(suit));
+                assert_eq!(value.to_string().parse::<Value>(), Ok(value));
+            }
+        }
+    }
 }
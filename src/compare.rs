@@ -0,0 +1,138 @@
+use std::cmp::Ordering;
+
+use crate::card::{value_counts, Card, Value};
+
+/// A sort order for cards, see `sort_cards_by`.
+pub trait CardOrd {
+    fn cmp(&self, a: &Card, b: &Card) -> Ordering;
+}
+
+/// Ascending by value, then by suit. Same as `Card`'s `Ord`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByValueThenSuit;
+
+/// Ascending by suit, then by value, for grouping suits together.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BySuitThenValue;
+
+/// Descending by value only; equal values keep their relative order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValueOnlyDesc;
+
+impl CardOrd for ByValueThenSuit {
+    fn cmp(&self, a: &Card, b: &Card) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl CardOrd for BySuitThenValue {
+    fn cmp(&self, a: &Card, b: &Card) -> Ordering {
+        (a.suit(), a.value()).cmp(&(b.suit(), b.value()))
+    }
+}
+
+impl CardOrd for ValueOnlyDesc {
+    fn cmp(&self, a: &Card, b: &Card) -> Ordering {
+        b.value().cmp(&a.value())
+    }
+}
+
+/// Stable sort of `cards` under the given order.
+pub fn sort_cards_by(cards: &mut [Card], ord: impl CardOrd) {
+    cards.sort_by(|a, b| ord.cmp(a, b));
+}
+
+/// Sorting and grouping helpers on card slices.
+pub trait CardsExt {
+    /// Highest value first; equal values keep their order.
+    fn sort_desc(&mut self);
+    /// Lowest value first; equal values keep their order.
+    fn sort_asc(&mut self);
+    /// Cards of each suit in input order, indexed by `Suit::index`.
+    fn group_by_suit(&self) -> [Vec<Card>; 4];
+    /// Cards of each value, most frequent first, then highest value first.
+    /// Cards within a group keep their input order.
+    fn group_by_value(&self) -> Vec<(Value, Vec<Card>)>;
+}
+
+impl CardsExt for [Card] {
+    fn sort_desc(&mut self) {
+        sort_cards_by(self, ValueOnlyDesc);
+    }
+
+    fn sort_asc(&mut self) {
+        self.sort_by_key(|c| c.value());
+    }
+
+    fn group_by_suit(&self) -> [Vec<Card>; 4] {
+        let mut groups: [Vec<Card>; 4] = Default::default();
+        for &card in self {
+            groups[card.suit().index() as usize].push(card);
+        }
+        groups
+    }
+
+    fn group_by_value(&self) -> Vec<(Value, Vec<Card>)> {
+        let counts = value_counts(self);
+        let mut groups: Vec<(Value, Vec<Card>)> = Value::ALL
+            .into_iter()
+            .rev()
+            .filter(|&v| counts[v as usize - 2] > 0)
+            .map(|v| (v, self.iter().filter(|c| c.value() == v).copied().collect()))
+            .collect();
+        groups.sort_by_key(|(_, cards)| std::cmp::Reverse(cards.len()));
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(ord: impl CardOrd) -> String {
+        let mut cards = Card::parse_many("9s Kd 2h 9h As 2c Kc").unwrap();
+        sort_cards_by(&mut cards, ord);
+        cards
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_sort() {
+        assert_eq!(sorted(ByValueThenSuit), "2h 2c 9h 9s Kd Kc As");
+        assert_eq!(sorted(BySuitThenValue), "2h 9h Kd 2c Kc 9s As");
+        assert_eq!(sorted(ValueOnlyDesc), "As Kd Kc 9s 9h 2h 2c");
+    }
+
+    #[test]
+    fn test_cards_ext() {
+        let mut cards = Card::parse_many("Ah 9h Kd 9s 5h 2h 7c").unwrap();
+        let show = |cards: &[Card]| {
+            cards
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let [hearts, diamonds, clubs, spades] = cards.group_by_suit();
+        assert_eq!(show(&hearts), "Ah 9h 5h 2h");
+        assert_eq!(show(&diamonds), "Kd");
+        assert_eq!(show(&clubs), "7c");
+        assert_eq!(show(&spades), "9s");
+
+        let groups: Vec<String> = cards
+            .group_by_value()
+            .iter()
+            .map(|(v, cards)| format!("{}:{}", v, show(cards)))
+            .collect();
+        assert_eq!(groups, ["9:9h 9s", "A:Ah", "K:Kd", "7:7c", "5:5h", "2:2h"]);
+
+        cards.sort_desc();
+        assert_eq!(show(&cards), "Ah Kd 9h 9s 7c 5h 2h");
+        cards.sort_asc();
+        assert_eq!(show(&cards), "2h 5h 7c 9h 9s Kd Ah");
+    }
+}
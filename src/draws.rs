@@ -0,0 +1,573 @@
+//! What a hand on the flop or turn can still become.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    card::{Card, Suit, Value},
+    error::Error,
+    holdem::check_duplicates,
+    HoldemHand, Rank, RankCategory,
+};
+
+/// Four cards of one suit, at least one of them in the hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlushDraw {
+    pub suit: Suit,
+    /// The hand holds the best card of the suit not on the board, so the
+    /// flush it makes is the best one possible.
+    pub nut: bool,
+    /// The unseen cards of the suit.
+    pub outs: Vec<Card>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StraightDrawKind {
+    /// Four in a row, completed at either end: 8 outs.
+    OpenEnded,
+    /// One value missing inside the straight: 4 outs.
+    Gutshot,
+    /// Two different inside values each complete a straight: 8 outs.
+    DoubleGutshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StraightDraw {
+    pub kind: StraightDrawKind,
+    /// The values that complete a straight, lowest first.
+    pub values: Vec<Value>,
+    /// The unseen cards of those values.
+    pub outs: Vec<Card>,
+}
+
+/// The draws of a hand, see `detect_draws`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Draws {
+    pub flush: Option<FlushDraw>,
+    pub straight: Option<StraightDraw>,
+}
+
+impl Draws {
+    /// A flush draw and a straight draw at once.
+    pub fn is_combo(&self) -> bool {
+        self.flush.is_some() && self.straight.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flush.is_none() && self.straight.is_none()
+    }
+
+    /// Every card completing any draw, each once, in ascending order.
+    pub fn outs(&self) -> Vec<Card> {
+        let flush = self.flush.iter().flat_map(|d| &d.outs);
+        let straight = self.straight.iter().flat_map(|d| &d.outs);
+        let mut outs: Vec<Card> = flush.chain(straight).copied().collect();
+        outs.sort_unstable();
+        outs.dedup();
+        outs
+    }
+}
+
+/// Flush and straight draws of `hole` on a flop or turn `board`.
+///
+/// A draw counts only when the hand takes part: the next card has to
+/// make a flush or straight with at least one hole card that the board
+/// alone would not make. A flush or straight the hand already has is not
+/// drawn to again.
+///
+/// ```
+/// use poker::{card::Card, draws::{detect_draws, StraightDrawKind}};
+///
+/// let cards = Card::parse_many("As Ts Ks 8s Jd").unwrap();
+/// let draws = detect_draws([cards[0], cards[1]], &cards[2..]).unwrap();
+/// assert!(draws.flush.as_ref().unwrap().nut);
+/// assert_eq!(draws.straight.unwrap().kind, StraightDrawKind::Gutshot);
+/// ```
+pub fn detect_draws(hole: [Card; 2], board: &[Card]) -> Result<Draws, Error> {
+    let unseen = unseen(&[&hole, board])?;
+    Ok(Draws {
+        flush: flush_draw(hole, board, &unseen),
+        straight: straight_draw(hole, board, &unseen),
+    })
+}
+
+fn flush_draw(hole: [Card; 2], board: &[Card], unseen: &[Card]) -> Option<FlushDraw> {
+    let suited = |suit: Suit, cards: &[Card]| cards.iter().filter(|c| c.suit() == suit).count();
+    let suit = Suit::ALL
+        .into_iter()
+        .find(|&suit| suited(suit, &hole) > 0 && suited(suit, &hole) + suited(suit, board) == 4)?;
+    let outs: Vec<Card> = unseen
+        .iter()
+        .copied()
+        .filter(|c| c.suit() == suit)
+        .collect();
+    let best_off_board = Card::all()
+        .filter(|c| c.suit() == suit && !board.contains(c))
+        .max()?;
+    Some(FlushDraw {
+        suit,
+        nut: hole.contains(&best_off_board),
+        outs,
+    })
+}
+
+fn straight_draw(hole: [Card; 2], board: &[Card], unseen: &[Card]) -> Option<StraightDraw> {
+    let hand = value_bits(hole.iter().chain(board));
+    let board = value_bits(board);
+    if best_straight(hand).is_some() {
+        return None;
+    }
+    let values: Vec<Value> = Value::ALL
+        .into_iter()
+        .filter(|&v| {
+            let bit = value_bit(v);
+            let made = best_straight(hand | bit);
+            made.is_some() && made > best_straight(board | bit)
+        })
+        .filter(|&v| unseen.iter().any(|c| c.value() == v))
+        .collect();
+    let kind = match values[..] {
+        [] => return None,
+        [_] => StraightDrawKind::Gutshot,
+        _ if open_ended(hand, &values) => StraightDrawKind::OpenEnded,
+        _ => StraightDrawKind::DoubleGutshot,
+    };
+    let outs = unseen
+        .iter()
+        .copied()
+        .filter(|c| values.contains(&c.value()))
+        .collect();
+    Some(StraightDraw { kind, values, outs })
+}
+
+// two completing values with the four values between them all held
+fn open_ended(hand: u16, values: &[Value]) -> bool {
+    values.iter().any(|&low| {
+        let run = (1..=4).all(|i| {
+            low.checked_add_rank(i)
+                .is_some_and(|v| hand & value_bit(v) != 0)
+        });
+        let high = low.checked_add_rank(5);
+        run && high.is_some_and(|high| values.contains(&high))
+    })
+}
+
+// bit `value` for each value held, and bit 1 for an ace played low
+fn value_bits<'a>(cards: impl IntoIterator<Item = &'a Card>) -> u16 {
+    cards
+        .into_iter()
+        .fold(0, |bits, c| bits | value_bit(c.value()))
+}
+
+fn value_bit(value: Value) -> u16 {
+    let bit = 1 << value as u16;
+    if value == Value::Ace {
+        bit | 1 << 1
+    } else {
+        bit
+    }
+}
+
+// the top value of the highest five in a row
+fn best_straight(bits: u16) -> Option<u8> {
+    (5..=14)
+        .rev()
+        .find(|&top| (top - 4..=top).all(|v| bits & 1 << v != 0))
+}
+
+/// Three cards of one suit on the flop, at least one in the hand, so a
+/// suited turn and river make a flush.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackdoorFlush {
+    pub suit: Suit,
+    /// The chance that both the turn and river are of the suit.
+    pub probability: f64,
+}
+
+/// Straights the hand makes only with both the turn and the river.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackdoorStraight {
+    /// The pairs of values completing one, lower value first.
+    pub runouts: Vec<[Value; 2]>,
+    /// The chance that the turn and river are one of those pairs.
+    pub probability: f64,
+}
+
+/// The runner-runner draws of a hand on the flop, see
+/// `detect_backdoor_draws`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackdoorDraws {
+    pub flush: Option<BackdoorFlush>,
+    pub straight: Option<BackdoorStraight>,
+    /// The chance that any of them completes, counting once the runouts
+    /// making both.
+    pub probability: f64,
+}
+
+/// Backdoor flush and straight draws of `hole` on `flop`, with their exact
+/// chances over the 1081 turn and river pairs.
+///
+/// A straight made by the turn or the river alone, such as an open-ended
+/// draw's, is no backdoor and is left out; so are straights and flushes
+/// the board makes without the hand.
+///
+/// ```
+/// use poker::{card::Card, draws::detect_backdoor_draws};
+///
+/// let cards = Card::parse_many("Ah Kh 7h 2c 9d").unwrap();
+/// let draws = detect_backdoor_draws([cards[0], cards[1]], [cards[2], cards[3], cards[4]]);
+/// let flush = draws.unwrap().flush.unwrap();
+/// assert!((flush.probability - 0.042).abs() < 0.001);
+/// ```
+pub fn detect_backdoor_draws(hole: [Card; 2], flop: [Card; 3]) -> Result<BackdoorDraws, Error> {
+    let unseen = unseen(&[&hole, &flop])?;
+    let suited = |suit: Suit| {
+        hole.iter()
+            .chain(&flop)
+            .filter(|c| c.suit() == suit)
+            .count()
+    };
+    let flush_suit = Suit::ALL
+        .into_iter()
+        .find(|&suit| suited(suit) == 3 && hole.iter().any(|c| c.suit() == suit));
+
+    let (hand, board) = (value_bits(hole.iter().chain(&flop)), value_bits(&flop));
+    let makes = |bits: u16| {
+        let made = best_straight(hand | bits);
+        made.is_some() && made > best_straight(board | bits)
+    };
+    let (mut flushes, mut straights, mut either, mut runouts) = (0, 0, 0, 0);
+    let mut straight_values = Vec::new();
+    for (i, &turn) in unseen.iter().enumerate() {
+        for &river in &unseen[i + 1..] {
+            runouts += 1;
+            let flush = flush_suit.is_some_and(|s| turn.suit() == s && river.suit() == s);
+            let (t, r) = (value_bit(turn.value()), value_bit(river.value()));
+            let straight = makes(t | r) && !makes(t) && !makes(r);
+            if straight {
+                let (low, high) = (
+                    turn.value().min(river.value()),
+                    turn.value().max(river.value()),
+                );
+                if !straight_values.contains(&[low, high]) {
+                    straight_values.push([low, high]);
+                }
+            }
+            flushes += u32::from(flush);
+            straights += u32::from(straight);
+            either += u32::from(flush || straight);
+        }
+    }
+    let chance = |n: u32| f64::from(n) / f64::from(runouts);
+    straight_values.sort_unstable();
+    Ok(BackdoorDraws {
+        flush: flush_suit.map(|suit| BackdoorFlush {
+            suit,
+            probability: chance(flushes),
+        }),
+        straight: (straights > 0).then(|| BackdoorStraight {
+            runouts: straight_values,
+            probability: chance(straights),
+        }),
+        probability: chance(either),
+    })
+}
+
+/// The next card's effect on a hand behind another, see `count_outs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outs {
+    /// Cards putting the hand ahead.
+    pub clean: Vec<Card>,
+    /// Cards lifting the hand's category but leaving it behind or tied,
+    /// such as a flush card that also pairs the board for a full house.
+    /// A card lifting the board as much, pairing it say, is no out.
+    pub tainted: Vec<Card>,
+    /// The clean and tainted outs by the category each gives the hand.
+    pub by_category: BTreeMap<RankCategory, Vec<Card>>,
+}
+
+impl Outs {
+    /// Number of clean outs.
+    pub fn count(&self) -> usize {
+        self.clean.len()
+    }
+}
+
+/// Deals each unseen card as the next board card and sorts out the ones
+/// that help `hole` against `villain` on a flop or turn `board`.
+///
+/// Outs are only counted for a hand behind or tied now; a hand already
+/// ahead has none. Cards are in ascending order.
+///
+/// ```
+/// use poker::{card::Card, draws::count_outs};
+///
+/// let cards = Card::parse_many("7h 6h Ks Jd Kh 9h 4c 2d").unwrap();
+/// let outs = count_outs([cards[0], cards[1]], &cards[4..], [cards[2], cards[3]]);
+/// // the nine hearts
+/// assert_eq!(outs.unwrap().count(), 9);
+/// ```
+pub fn count_outs(hole: [Card; 2], board: &[Card], villain: [Card; 2]) -> Result<Outs, Error> {
+    let unseen = unseen(&[&hole, &villain, board])?;
+    let rank = |hand: [Card; 2], card: Option<Card>| -> Result<Rank, Error> {
+        let mut cards = hand.to_vec();
+        cards.extend_from_slice(board);
+        cards.extend(card);
+        Ok(HoldemHand::best_from(&cards)?.rank())
+    };
+    let (now, against) = (rank(hole, None)?, rank(villain, None)?);
+    let mut outs = Outs::default();
+    if now > against {
+        return Ok(outs);
+    }
+    for card in unseen {
+        let (next, against) = (rank(hole, Some(card))?, rank(villain, Some(card))?);
+        let out = if next > against {
+            &mut outs.clean
+        } else if improves(now, next, board, card) {
+            &mut outs.tainted
+        } else {
+            continue;
+        };
+        out.push(card);
+        outs.by_category
+            .entry(next.category())
+            .or_default()
+            .push(card);
+    }
+    Ok(outs)
+}
+
+/// The unseen cards that lift the category of `hole`'s best hand on a
+/// flop or turn `board`, by the category they give it, when the other
+/// hands are unknown.
+///
+/// Only the category is compared, and a card counts only when the hand
+/// ends up above what the board and that card make alone: a card pairing
+/// the board makes every hand a pair and lifts none.
+pub fn improving_cards(
+    hole: [Card; 2],
+    board: &[Card],
+) -> Result<BTreeMap<RankCategory, Vec<Card>>, Error> {
+    let unseen = unseen(&[&hole, board])?;
+    let mut cards = hole.to_vec();
+    cards.extend_from_slice(board);
+    let now = HoldemHand::best_from(&cards)?.rank();
+    let mut improving: BTreeMap<RankCategory, Vec<Card>> = BTreeMap::new();
+    for card in unseen {
+        cards.push(card);
+        let next = HoldemHand::best_from(&cards)?.rank();
+        cards.pop();
+        if improves(now, next, board, card) {
+            improving.entry(next.category()).or_default().push(card);
+        }
+    }
+    Ok(improving)
+}
+
+// `card` lifts the hand's category from `now` to `next`, above what the
+// board and the card alone make
+fn improves(now: Rank, next: Rank, board: &[Card], card: Card) -> bool {
+    let mut alone = board.to_vec();
+    alone.push(card);
+    next.category() > now.category() && next.category() > board_category(&alone)
+}
+
+// the category of four or five board cards; four cannot be a straight or
+// flush, so only their values count
+fn board_category(cards: &[Card]) -> RankCategory {
+    if let Ok(hand) = HoldemHand::best_from(cards) {
+        return hand.rank().category();
+    }
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[card.value() as usize] += 1;
+    }
+    let pairs = counts.iter().filter(|&&n| n == 2).count();
+    match counts.iter().max() {
+        Some(4) => RankCategory::Bomb,
+        Some(3) => RankCategory::Set,
+        _ if pairs == 2 => RankCategory::TwoPair,
+        _ if pairs == 1 => RankCategory::Pair,
+        _ => RankCategory::HighCard,
+    }
+}
+
+// the cards not in any of `seen`, checking the board is a flop or turn
+fn unseen(seen: &[&[Card]]) -> Result<Vec<Card>, Error> {
+    let board = seen[seen.len() - 1];
+    if !(3..=4).contains(&board.len()) {
+        return Err(Error::BadCard(format!(
+            "invalid board of {} cards",
+            board.len()
+        )));
+    }
+    let seen = seen.concat();
+    check_duplicates(&seen)?;
+    Ok(Card::all().filter(|c| !seen.contains(c)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draws(s: &str) -> Draws {
+        let cards = Card::parse_many(s).unwrap();
+        detect_draws([cards[0], cards[1]], &cards[2..]).unwrap()
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        let mut cards = Card::parse_many(s).unwrap();
+        cards.sort();
+        cards
+    }
+
+    #[test]
+    fn test_combo_draw() {
+        let found = draws("As Ts Ks 8s Jd");
+        let flush = found.flush.as_ref().unwrap();
+        assert_eq!(
+            (flush.suit, flush.nut, flush.outs.len()),
+            (Suit::Spade, true, 9)
+        );
+        let straight = found.straight.as_ref().unwrap();
+        assert_eq!(straight.kind, StraightDrawKind::Gutshot);
+        assert_eq!(straight.values, [Value::Queen]);
+        assert_eq!(straight.outs, cards("Qh Qd Qc Qs"));
+        assert!(found.is_combo());
+        // the queen of spades completes both
+        assert_eq!(found.outs().len(), 12);
+
+        let flush = draws("Ks 2s 9s 4s Jd").flush.unwrap();
+        assert!(!flush.nut);
+        assert!(draws("Ks 2s As 9s 4d").flush.unwrap().nut);
+    }
+
+    #[test]
+    fn test_straight_draws() {
+        let open = draws("8h 7h 6c 5d Kd").straight.unwrap();
+        assert_eq!(open.kind, StraightDrawKind::OpenEnded);
+        assert_eq!(open.values, [Value::Four, Value::Nine]);
+        assert_eq!(open.outs.len(), 8);
+
+        let double = draws("9h 5d 7c 6s 3h").straight.unwrap();
+        assert_eq!(double.kind, StraightDrawKind::DoubleGutshot);
+        assert_eq!(double.values, [Value::Four, Value::Eight]);
+        assert_eq!(double.outs.len(), 8);
+
+        let wheel = draws("Ah 2d 3c 4s Kd").straight.unwrap();
+        assert_eq!(wheel.kind, StraightDrawKind::Gutshot);
+        assert_eq!(wheel.values, [Value::Five]);
+        // a nine or an ace completes king to ten
+        let broadway = draws("Kh Qd Jc Ts 2d").straight.unwrap();
+        assert_eq!(broadway.kind, StraightDrawKind::OpenEnded);
+        assert_eq!(broadway.values, [Value::Nine, Value::Ace]);
+        // one end only: ace to jack needs the ten
+        assert_eq!(
+            draws("Ah Kd Qc Js 2d").straight.unwrap().kind,
+            StraightDrawKind::Gutshot
+        );
+    }
+
+    #[test]
+    fn test_made_and_board_draws() {
+        // already made, or only the board drawing
+        assert!(draws("8h 7h 6c 5d 4s").straight.is_none());
+        assert!(draws("Ah 2h Kh 7h 4h").flush.is_none());
+        assert!(draws("Ac Ad 9c 8d 7h 6s").is_empty());
+        assert!(draws("Ac Kd 9h 8h 7h 6h").flush.is_none());
+        // a made straight still draws to a flush
+        let found = draws("8h 7h 6h 5h 9d");
+        assert!(found.straight.is_none());
+        assert_eq!(found.flush.unwrap().outs.len(), 9);
+
+        let hole = [Card::ALL[0], Card::ALL[1]];
+        let err = detect_draws(hole, &Card::ALL[2..4]);
+        assert_eq!(
+            err,
+            Err(Error::BadCard("invalid board of 2 cards".to_string()))
+        );
+        let err = detect_draws(hole, &Card::ALL[1..4]);
+        assert_eq!(err, Err(Error::DuplicateCard(Card::ALL[1])));
+    }
+
+    #[test]
+    fn test_outs() {
+        let two = |s: &str| -> [Card; 2] { Card::parse_many(s).unwrap().try_into().unwrap() };
+        let board = Card::parse_many("Kh 9h 4c 2d").unwrap();
+        let hearts = cards("Ah Qh Jh Th 8h 5h 4h 3h 2h");
+
+        // a flush draw against top pair: the hearts win, pairing only loses
+        let outs = count_outs(two("7h 6h"), &board, two("Ks Jd")).unwrap();
+        assert_eq!(outs.clean, hearts);
+        assert_eq!(outs.count(), 9);
+        assert_eq!(outs.tainted, cards("6d 6c 6s 7d 7c 7s"));
+        assert_eq!(outs.by_category[&RankCategory::Flush], hearts);
+        assert_eq!(outs.by_category[&RankCategory::Pair].len(), 6);
+
+        // against a set the hearts pairing the board fill it up
+        let outs = count_outs(two("7h 6h"), &board, two("Ks Kd")).unwrap();
+        assert_eq!(outs.clean, cards("Ah Qh Jh Th 8h 5h 3h"));
+        assert!(outs.tainted.contains(&cards("4h")[0]));
+        assert!(outs.tainted.contains(&cards("2h")[0]));
+
+        // drawing dead to a royal flush, and no outs when ahead
+        let royal = Card::parse_many("As Ks Qs Js").unwrap();
+        let dead = count_outs(two("7c 2d"), &royal, two("Ts 3h")).unwrap();
+        assert_eq!(dead.count(), 0);
+        assert_eq!(
+            count_outs(two("Ks Jd"), &board, two("7h 6h")),
+            Ok(Outs::default())
+        );
+
+        // on the flop too, and any card the villain holds is not an out
+        let flop = &board[..3];
+        let outs = count_outs(two("7h 6h"), flop, two("Ks Jh")).unwrap();
+        assert_eq!(outs.count(), 8);
+        let err = count_outs(two("7h 6h"), flop, two("Ks Kh"));
+        assert_eq!(err, Err(Error::DuplicateCard(cards("Kh")[0])));
+    }
+
+    #[test]
+    fn test_improving_cards() {
+        let board = Card::parse_many("Kh 9h 4c 2d").unwrap();
+        let hole = [cards("7h")[0], cards("6h")[0]];
+        let improving = improving_cards(hole, &board).unwrap();
+        assert_eq!(improving[&RankCategory::Flush].len(), 9);
+        // the sixes and sevens, not the cards pairing the board
+        assert_eq!(improving[&RankCategory::Pair], cards("6d 6c 6s 7d 7c 7s"));
+        assert_eq!(improving.len(), 2);
+    }
+
+    #[test]
+    fn test_backdoor_draws() {
+        let backdoor = |s: &str| {
+            let cards = Card::parse_many(s).unwrap();
+            detect_backdoor_draws([cards[0], cards[1]], [cards[2], cards[3], cards[4]]).unwrap()
+        };
+        // both of the ten hearts left, out of 47 * 46 / 2 runouts
+        let draws = backdoor("Ah Kh 7h 2c 9d");
+        let flush = draws.flush.unwrap();
+        assert_eq!(flush.suit, Suit::Heart);
+        assert_eq!(flush.probability, 45.0 / 1081.0);
+        assert!(draws.straight.is_none());
+        assert_eq!(draws.probability, flush.probability);
+
+        // 8 7 6 wants 9 T, 5 9 or 4 5
+        let draws = backdoor("8h 7h 6h Kc 2d");
+        let straight = draws.straight.as_ref().unwrap();
+        use Value::*;
+        assert_eq!(straight.runouts, [[Four, Five], [Five, Nine], [Nine, Ten]]);
+        assert_eq!(straight.probability, 3.0 * 16.0 / 1081.0);
+        // runouts like 9h Th make both
+        let flush = draws.flush.unwrap();
+        assert!(draws.probability < flush.probability + straight.probability);
+        assert!(draws.probability > flush.probability.max(straight.probability));
+
+        // a frontdoor flush draw and an open-ended draw are no backdoors
+        let draws = backdoor("8h 7h 6h 5h 2d");
+        assert!(draws.flush.is_none() && draws.straight.is_none());
+        assert_eq!(draws.probability, 0.0);
+        // nor is a flush or straight only the board can make
+        assert_eq!(backdoor("Ac Kd 7h 6h 2h").flush, None);
+    }
+}
@@ -0,0 +1,1149 @@
+//! Players' equity over the ways a board can run out.
+//!
+//! Runouts are evaluated with a shared `LookupTable`, built on first use.
+//! With the `parallel` feature the `_par` variants spread the work over
+//! rayon's thread pool and return exactly what the serial functions do:
+//! exhaustive runs split by the first card dealt, and Monte Carlo runs
+//! draw each block of `CHUNK` iterations from its own stream, seeded from
+//! the caller's seed and the block's index, whichever thread runs it.
+
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign},
+    sync::OnceLock,
+};
+
+use crate::{
+    card::{Card, CardSet},
+    error::Error,
+    holdem::check_duplicates,
+    lookup::LookupTable,
+    range::Range,
+    HoldemHand,
+};
+
+// Monte Carlo iterations per random stream
+const CHUNK: u32 = 4096;
+
+/// How often a player wins, ties and loses over a set of runouts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Equity {
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+    /// Pots won through ties: a half for each two-way tie, a third for
+    /// each three-way tie and so on.
+    pub tie_share: f64,
+}
+
+impl Equity {
+    /// Runouts counted.
+    pub fn total(&self) -> u64 {
+        self.wins + self.ties + self.losses
+    }
+
+    pub fn win(&self) -> f64 {
+        self.fraction(self.wins as f64)
+    }
+
+    pub fn tie(&self) -> f64 {
+        self.fraction(self.ties as f64)
+    }
+
+    pub fn loss(&self) -> f64 {
+        self.fraction(self.losses as f64)
+    }
+
+    /// Share of the pot: wins plus the split pots from ties. The players
+    /// of one deal have equities summing to 1.
+    pub fn equity(&self) -> f64 {
+        self.fraction(self.wins as f64 + self.tie_share)
+    }
+
+    fn fraction(&self, n: f64) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => n / total as f64,
+        }
+    }
+
+    fn record_tie(&mut self, players: usize) {
+        self.ties += 1;
+        self.tie_share += 1.0 / players as f64;
+    }
+}
+
+impl Add for Equity {
+    type Output = Equity;
+
+    fn add(self, rhs: Equity) -> Equity {
+        Equity {
+            wins: self.wins + rhs.wins,
+            ties: self.ties + rhs.ties,
+            losses: self.losses + rhs.losses,
+            tie_share: self.tie_share + rhs.tie_share,
+        }
+    }
+}
+
+impl AddAssign for Equity {
+    fn add_assign(&mut self, rhs: Equity) {
+        *self = *self + rhs;
+    }
+}
+
+/// Counts heads-up showdowns, ties splitting the pot in two.
+impl FromIterator<Outcome> for Equity {
+    fn from_iter<I: IntoIterator<Item = Outcome>>(outcomes: I) -> Self {
+        let mut equity = Equity::default();
+        for outcome in outcomes {
+            match outcome {
+                Outcome::Win => equity.wins += 1,
+                Outcome::Tie => equity.record_tie(2),
+                Outcome::Loss => equity.losses += 1,
+            }
+        }
+        equity
+    }
+}
+
+/// The hero's result of one heads-up showdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Tie,
+    Loss,
+}
+
+/// Exact equity of `hero` against `villain` over every completion of
+/// `board`, which holds 0, 3, 4 or 5 cards: C(48, 5) runouts preflop,
+/// C(45, 2) on the flop and 44 on the turn. No card may appear twice.
+///
+/// ```
+/// use poker::{card::Card, equity::equity_exhaustive};
+///
+/// let cards = |s| Card::parse_many(s).unwrap();
+/// let hero = cards("As Ks").try_into().unwrap();
+/// let villain = cards("Qh Qd").try_into().unwrap();
+/// let equity = equity_exhaustive(hero, villain, &cards("Js 7s 2c")).unwrap();
+/// assert_eq!((equity.wins, equity.ties, equity.losses), (539, 0, 451));
+/// ```
+pub fn equity_exhaustive(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: &[Card],
+) -> Result<Equity, Error> {
+    let deal = Deal::new(&[hero, villain], board)?;
+    Ok(deal.exhaustive()[0])
+}
+
+/// Equity of `hero` against `villain` over `iterations` random
+/// completions of `board`, dealt from the cards nobody holds. The same
+/// `seed` gives the same result.
+///
+/// ```
+/// use poker::{card::Card, equity::equity_monte_carlo};
+///
+/// let cards = |s| Card::parse_many(s).unwrap().try_into().unwrap();
+/// let equity = equity_monte_carlo(cards("As Ad"), cards("Kh Kd"), &[], 10_000, 7).unwrap();
+/// assert_eq!(equity.total(), 10_000);
+/// assert!(equity.win() > 0.75 && equity.loss() > 0.15);
+/// ```
+pub fn equity_monte_carlo(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: &[Card],
+    iterations: u32,
+    seed: u64,
+) -> Result<Equity, Error> {
+    let deal = Deal::new(&[hero, villain], board)?;
+    Ok(deal.monte_carlo(iterations, seed)[0])
+}
+
+/// Exact equity of `hero` against `villain` before the flop, on the
+/// flop, on the turn and on the river of `board`. The river entry is the
+/// showdown itself: one win, one tie or one loss.
+pub fn equity_by_street(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: [Card; 5],
+) -> Result<[Equity; 4], Error> {
+    let mut streets = [Equity::default(); 4];
+    for (equity, known) in streets.iter_mut().zip([0, 3, 4, 5]) {
+        *equity = equity_exhaustive(hero, villain, &board[..known])?;
+    }
+    Ok(streets)
+}
+
+/// The showdown of `hero` against `villain` on each river that can fall
+/// on `board`, in card order. Collecting the outcomes into an `Equity`
+/// counts the winning, chopping and losing rivers.
+///
+/// ```
+/// use poker::{card::Card, equity::{river_distribution, Equity}};
+///
+/// let cards = |s| Card::parse_many(s).unwrap();
+/// let (hero, villain) = (cards("Ah 5h"), cards("Kc Qd"));
+/// let board = cards("Kh 9h 2c 7s").try_into().unwrap();
+/// let rivers = river_distribution([hero[0], hero[1]], [villain[0], villain[1]], board).unwrap();
+/// let summary: Equity = rivers.iter().map(|&(_, outcome)| outcome).collect();
+/// assert_eq!((summary.wins, summary.ties, summary.losses), (12, 0, 32));
+/// ```
+pub fn river_distribution(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: [Card; 4],
+) -> Result<Vec<(Card, Outcome)>, Error> {
+    let deal = Deal::new(&[hero, villain], &board)?;
+    let mut river = deal.board;
+    let rivers = deal.deck.iter().map(|&card| {
+        river[4] = card;
+        let mut equities = deal.none();
+        deal.showdown(&river, &mut equities);
+        let outcome = match equities[0] {
+            Equity { wins: 1, .. } => Outcome::Win,
+            Equity { ties: 1, .. } => Outcome::Tie,
+            _ => Outcome::Loss,
+        };
+        (card, outcome)
+    });
+    Ok(rivers.collect())
+}
+
+/// The hero's share of the pot against `villain` on each river that can
+/// fall on `board`, in card order, weighting the villain's combos as
+/// `equity_vs_range` does. Rivers leaving the villain no combo are left
+/// out.
+pub fn river_distribution_vs_range(
+    hero: [Card; 2],
+    villain: &Range,
+    board: [Card; 4],
+) -> Result<Vec<(Card, f64)>, Error> {
+    let dead: CardSet = hero.into_iter().chain(board).collect();
+    check_duplicates(&[&hero[..], &board].concat())?;
+    let [a, b, c, d] = board;
+    let mut rivers = Vec::new();
+    for river in CardSet::full() - dead {
+        let rank = |[x, y]: [Card; 2]| table().eval7(&[x, y, a, b, c, d, river]);
+        let mine = rank(hero);
+        let (mut share, mut weight) = (0.0, 0.0);
+        for (combo, w) in villain.iter_weighted() {
+            if combo.iter().any(|&c| c == river || dead.contains(c)) {
+                continue;
+            }
+            // lower class indices are stronger hands
+            share += w * match mine.cmp(&rank(combo)) {
+                Ordering::Less => 1.0,
+                Ordering::Equal => 0.5,
+                Ordering::Greater => 0.0,
+            };
+            weight += w;
+        }
+        if weight > 0.0 {
+            rivers.push((river, share / weight));
+        }
+    }
+    Ok(rivers)
+}
+
+/// `equity_exhaustive` on rayon's thread pool.
+#[cfg(feature = "parallel")]
+pub fn equity_exhaustive_par(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: &[Card],
+) -> Result<Equity, Error> {
+    use rayon::prelude::*;
+    let deal = Deal::new(&[hero, villain], board)?;
+    let equities = deal
+        .branches()
+        .into_par_iter()
+        .map(|first| deal.exhaustive_branch(first))
+        .reduce(|| deal.none(), add_all);
+    Ok(equities[0])
+}
+
+/// `equity_monte_carlo` on rayon's thread pool, with the same result for
+/// the same seed.
+#[cfg(feature = "parallel")]
+pub fn equity_monte_carlo_par(
+    hero: [Card; 2],
+    villain: [Card; 2],
+    board: &[Card],
+    iterations: u32,
+    seed: u64,
+) -> Result<Equity, Error> {
+    use rayon::prelude::*;
+    let deal = Deal::new(&[hero, villain], board)?;
+    let equities = (0..iterations.div_ceil(CHUNK))
+        .into_par_iter()
+        .map(|chunk| deal.monte_carlo_chunk(seed, chunk, iterations))
+        .reduce(|| deal.none(), add_all);
+    Ok(equities[0])
+}
+
+/// How `equity_vs_range` and `equity_multiway` count runouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquityMethod {
+    /// Every runout (of every pair of combos); practical from the flop on.
+    Exhaustive,
+    /// `iterations` random runouts (each for a random pair of combos).
+    MonteCarlo { iterations: u32, seed: u64 },
+}
+
+/// Each player's equity when all of `players` see the board out. Ties
+/// split the pot evenly between the tied players, so the equities of one
+/// deal sum to 1.
+///
+/// ```
+/// use poker::{card::Card, equity::{equity_multiway, EquityMethod}};
+///
+/// let cards = |s| Card::parse_many(s).unwrap().try_into().unwrap();
+/// let players = [cards("As Ad"), cards("Kh Kd"), cards("Qs Qc")];
+/// let board = Card::parse_many("2c 7h 9d").unwrap();
+/// let equities = equity_multiway(&players, &board, EquityMethod::Exhaustive).unwrap();
+/// let sum: f64 = equities.iter().map(|e| e.equity()).sum();
+/// assert!((sum - 1.0).abs() < 1e-9);
+/// assert!(equities[0].equity() > 0.8);
+/// ```
+pub fn equity_multiway(
+    players: &[[Card; 2]],
+    board: &[Card],
+    method: EquityMethod,
+) -> Result<Vec<Equity>, Error> {
+    let deal = Deal::new(players, board)?;
+    Ok(match method {
+        EquityMethod::Exhaustive => deal.exhaustive(),
+        EquityMethod::MonteCarlo { iterations, seed } => deal.monte_carlo(iterations, seed),
+    })
+}
+
+/// The result of `equity_vs_range`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RangeEquity {
+    /// All runouts counted, over every pair of combos, whatever their
+    /// weights.
+    pub overall: Equity,
+    /// The hero's combos that met any villain combo, with their runouts.
+    pub combos: Vec<([Card; 2], Equity)>,
+    // pot share and runouts, each runout weighted by its pair of combos
+    share: f64,
+    weight: f64,
+}
+
+impl RangeEquity {
+    /// The hero's share of the pot over the whole range, each runout
+    /// weighted by the product of the two combos' weights. With every
+    /// weight 1 this is `overall.equity()`.
+    pub fn equity(&self) -> f64 {
+        match self.weight {
+            0.0 => 0.0,
+            weight => self.share / weight,
+        }
+    }
+
+    fn record(&mut self, weight: f64, equity: &Equity) {
+        self.share += weight * (equity.wins as f64 + equity.tie_share);
+        self.weight += weight * equity.total() as f64;
+    }
+}
+
+/// Equity of every combo in `hero` against `villain` on `board`.
+///
+/// Combos that share a card with the board are left out, as is every
+/// pair of hero and villain combos sharing a card, so card removal
+/// weights the villain's range: with an ace in the hero's hand only three
+/// combos of aces remain. Exhaustively, every remaining pair counts the
+/// same number of runouts and so weighs the same, before the combos' own
+/// weights are applied by `RangeEquity::equity`.
+pub fn equity_vs_range(
+    hero: &Range,
+    villain: &Range,
+    board: &[Card],
+    method: EquityMethod,
+) -> Result<RangeEquity, Error> {
+    check_duplicates(board)?;
+    let live = |range: &Range| -> Vec<[Card; 2]> {
+        let range = range.iter();
+        range
+            .filter(|combo| !combo.iter().any(|c| board.contains(c)))
+            .collect()
+    };
+    let villains = live(villain);
+    let pairs: Vec<([Card; 2], Vec<[Card; 2]>)> = live(hero)
+        .into_iter()
+        .map(|h| {
+            let open = villains.iter().filter(|v| !v.iter().any(|c| h.contains(c)));
+            (h, open.copied().collect::<Vec<_>>())
+        })
+        .filter(|(_, villains)| !villains.is_empty())
+        .collect();
+
+    let mut result = RangeEquity {
+        combos: Vec::with_capacity(pairs.len()),
+        ..Default::default()
+    };
+    match method {
+        EquityMethod::Exhaustive => {
+            for (h, villains) in &pairs {
+                let mut equity = Equity::default();
+                for &v in villains {
+                    let pair = equity_exhaustive(*h, v, board)?;
+                    equity += pair;
+                    result.record(hero.weight(*h) * villain.weight(v), &pair);
+                }
+                result.combos.push((*h, equity));
+            }
+        }
+        EquityMethod::MonteCarlo { iterations, seed } => {
+            let fresh = pairs.iter().map(|(h, _)| (*h, Equity::default()));
+            result.combos.extend(fresh);
+            // pick pairs uniformly by their index among all pairs
+            let mut starts = Vec::with_capacity(pairs.len());
+            let mut total = 0;
+            for (_, villains) in &pairs {
+                starts.push(total);
+                total += villains.len();
+            }
+            let mut rng = SplitMix::new(seed, 0);
+            let iterations = if total == 0 { 0 } else { iterations };
+            for _ in 0..iterations {
+                let pick = rng.below(total);
+                let h = starts.partition_point(|&start| start <= pick) - 1;
+                let v = pairs[h].1[pick - starts[h]];
+                let deal = Deal::new(&[pairs[h].0, v], board)?;
+                let mut deck = deal.deck.clone();
+                let mut equities = [Equity::default(); 2];
+                deal.random_showdown(&mut deck, &mut rng, &mut equities);
+                result.combos[h].1 += equities[0];
+                result.record(hero.weight(pairs[h].0) * villain.weight(v), &equities[0]);
+            }
+        }
+    }
+    result.overall = result
+        .combos
+        .iter()
+        .map(|(_, e)| *e)
+        .fold(Equity::default(), Add::add);
+    Ok(result)
+}
+
+/// Where the hero stands against one villain combo, indexing the rows
+/// and columns of `Ehs::table`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Standing {
+    Ahead,
+    Tied,
+    Behind,
+}
+
+impl Standing {
+    // from the hands' class indices, lower ones stronger
+    fn of(hero: u16, villain: u16) -> Standing {
+        match hero.cmp(&villain) {
+            Ordering::Less => Standing::Ahead,
+            Ordering::Equal => Standing::Tied,
+            Ordering::Greater => Standing::Behind,
+        }
+    }
+}
+
+/// Effective hand strength, after Billings et al.: the hand's strength
+/// against a range now and its potential to pull ahead or fall behind.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Ehs {
+    /// The share of the villain's combos the hand beats now, ties counting
+    /// half.
+    pub hs: f64,
+    /// The chance of ending ahead when behind now, ties counting half.
+    pub ppot: f64,
+    /// The chance of ending behind when ahead now, ties counting half.
+    pub npot: f64,
+    /// Villain combos by where the hero stands now, indexed by `Standing`
+    /// and weighted as in the range.
+    pub now: [f64; 3],
+    /// Pairs of villain combo and runout by where the hero stands now,
+    /// then after the runout, each weighted as its combo.
+    pub table: [[f64; 3]; 3],
+}
+
+impl Ehs {
+    /// `hs * (1 - npot) + (1 - hs) * ppot`.
+    pub fn ehs(&self) -> f64 {
+        self.hs * (1.0 - self.npot) + (1.0 - self.hs) * self.ppot
+    }
+}
+
+/// The effective hand strength of `hole` against `villain` on a flop, turn
+/// or river, running the board out to the river. On the river there is
+/// no potential left.
+///
+/// ```
+/// use poker::{card::Card, equity::ehs, range::Range};
+///
+/// let cards = Card::parse_many("8h 7h 9h 6h 2c").unwrap();
+/// let villain = Range::parse("AA, KK, AKs").unwrap();
+/// let ehs = ehs([cards[0], cards[1]], &cards[2..], &villain).unwrap();
+/// assert!(ehs.hs < 0.1 && ehs.ppot > 0.5);
+/// ```
+pub fn ehs(hole: [Card; 2], board: &[Card], villain: &Range) -> Result<Ehs, Error> {
+    hand_potential(hole, board, villain, 5)
+}
+
+/// `ehs` looking one card ahead only: on the flop the potential is that
+/// of the turn, which is faster and undercounts runner-runner hands.
+pub fn ehs_one_street(hole: [Card; 2], board: &[Card], villain: &Range) -> Result<Ehs, Error> {
+    hand_potential(hole, board, villain, (board.len() + 1).min(5))
+}
+
+// `ehs` running the board out to `until` cards
+fn hand_potential(
+    hole: [Card; 2],
+    board: &[Card],
+    villain: &Range,
+    until: usize,
+) -> Result<Ehs, Error> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(Error::BadCard(format!(
+            "invalid board of {} cards",
+            board.len()
+        )));
+    }
+    check_duplicates(&[&hole[..], board].concat())?;
+    let dead: CardSet = hole.into_iter().chain(board.iter().copied()).collect();
+    let villains: Vec<([Card; 2], f64)> = villain
+        .iter_weighted()
+        .filter(|(combo, _)| !combo.iter().any(|&c| dead.contains(c)))
+        .collect();
+
+    let mut result = Ehs::default();
+    let mut hero = [&hole[..], board].concat();
+    let mut other = hero.clone();
+    let mine = strength(&hero);
+    let mut now = Vec::with_capacity(villains.len());
+    for &(combo, weight) in &villains {
+        other[..2].copy_from_slice(&combo);
+        let standing = Standing::of(mine, strength(&other));
+        result.now[standing as usize] += weight;
+        now.push(standing);
+    }
+
+    let deck: Vec<Card> = (CardSet::full() - dead).iter().collect();
+    let mut runout = Vec::new();
+    for_each_runout(&deck, until - board.len(), &mut runout, &mut |runout| {
+        hero.truncate(2 + board.len());
+        hero.extend(runout);
+        other.clone_from(&hero);
+        let mine = strength(&hero);
+        for (&(combo, weight), &now) in villains.iter().zip(&now) {
+            if combo.iter().any(|c| runout.contains(c)) {
+                continue;
+            }
+            other[..2].copy_from_slice(&combo);
+            let then = Standing::of(mine, strength(&other));
+            result.table[now as usize][then as usize] += weight;
+        }
+    });
+
+    let [ahead, tied, behind] = result.now;
+    result.hs = ratio(ahead + tied / 2.0, ahead + tied + behind);
+    let [ahead, tied, behind] = result.table;
+    let row = |r: [f64; 3]| r.iter().sum::<f64>();
+    result.ppot = ratio(
+        behind[0] + behind[1] / 2.0 + tied[0] / 2.0,
+        row(behind) + row(tied) / 2.0,
+    );
+    result.npot = ratio(
+        ahead[2] + tied[2] / 2.0 + ahead[1] / 2.0,
+        row(ahead) + row(tied) / 2.0,
+    );
+    Ok(result)
+}
+
+// calls `f` with every set of `n` cards from `deck`
+fn for_each_runout(deck: &[Card], n: usize, runout: &mut Vec<Card>, f: &mut impl FnMut(&[Card])) {
+    if n == 0 {
+        f(runout);
+        return;
+    }
+    for (i, &card) in deck.iter().enumerate() {
+        runout.push(card);
+        for_each_runout(&deck[i + 1..], n - 1, runout, f);
+        runout.pop();
+    }
+}
+
+// the class index of the best hand in five to seven distinct cards
+fn strength(cards: &[Card]) -> u16 {
+    match *cards {
+        [a, b, c, d, e, f, g] => table().eval7(&[a, b, c, d, e, f, g]),
+        _ => HoldemHand::best_from(cards).map_or(u16::MAX, |h| h.rank().class_index()),
+    }
+}
+
+fn ratio(n: f64, total: f64) -> f64 {
+    if total == 0.0 {
+        0.0
+    } else {
+        n / total
+    }
+}
+
+fn table() -> &'static LookupTable {
+    static TABLE: OnceLock<LookupTable> = OnceLock::new();
+    TABLE.get_or_init(LookupTable::build)
+}
+
+fn add_all(mut a: Vec<Equity>, b: Vec<Equity>) -> Vec<Equity> {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a += b;
+    }
+    a
+}
+
+// the most players five board cards leave enough cards for
+const MAX_PLAYERS: usize = (52 - 5) / 2;
+
+// players' hole cards and a partial board, to be run out
+struct Deal {
+    players: Vec<[Card; 2]>,
+    board: [Card; 5],
+    // board cards already known
+    known: usize,
+    // the cards left to deal from
+    deck: Vec<Card>,
+}
+
+impl Deal {
+    fn new(players: &[[Card; 2]], board: &[Card]) -> Result<Self, Error> {
+        if !(2..=MAX_PLAYERS).contains(&players.len()) {
+            return Err(Error::BadCard(format!(
+                "invalid number of players: {}",
+                players.len()
+            )));
+        }
+        if !matches!(board.len(), 0 | 3 | 4 | 5) {
+            return Err(Error::BadCard(format!(
+                "invalid board of {} cards",
+                board.len()
+            )));
+        }
+        let known: Vec<Card> = players.iter().flatten().chain(board).copied().collect();
+        check_duplicates(&known)?;
+        let dead: CardSet = known.into_iter().collect();
+        let mut cards = [players[0][0]; 5];
+        cards[..board.len()].copy_from_slice(board);
+        Ok(Deal {
+            players: players.to_vec(),
+            board: cards,
+            known: board.len(),
+            deck: (CardSet::full() - dead).iter().collect(),
+        })
+    }
+
+    // nothing counted yet, for every player
+    fn none(&self) -> Vec<Equity> {
+        vec![Equity::default(); self.players.len()]
+    }
+
+    // records the result of a complete board for every player
+    fn showdown(&self, board: &[Card; 5], equities: &mut [Equity]) {
+        let [a, b, c, d, e] = *board;
+        let mut ranks = [0; MAX_PLAYERS];
+        let ranks = &mut ranks[..self.players.len()];
+        for (rank, &[x, y]) in ranks.iter_mut().zip(&self.players) {
+            *rank = table().eval7(&[x, y, a, b, c, d, e]);
+        }
+        // lower class indices are stronger hands
+        let best = ranks.iter().copied().min().unwrap_or_default();
+        let winners = ranks.iter().filter(|&&rank| rank == best).count();
+        for (equity, &rank) in equities.iter_mut().zip(ranks.iter()) {
+            match (rank == best, winners) {
+                (false, _) => equity.losses += 1,
+                (true, 1) => equity.wins += 1,
+                (true, _) => equity.record_tie(winners),
+            }
+        }
+    }
+
+    fn exhaustive(&self) -> Vec<Equity> {
+        let branches = self.branches().map(|first| self.exhaustive_branch(first));
+        branches.fold(self.none(), add_all)
+    }
+
+    // the independent pieces of an exhaustive run, see `exhaustive_branch`
+    fn branches(&self) -> std::ops::Range<usize> {
+        match self.known {
+            5 => 0..1,
+            _ => 0..self.deck.len(),
+        }
+    }
+
+    // every runout whose first new card is deck[first]
+    fn exhaustive_branch(&self, first: usize) -> Vec<Equity> {
+        let mut equities = self.none();
+        let mut board = self.board;
+        if self.known == 5 {
+            self.showdown(&board, &mut equities);
+        } else {
+            board[self.known] = self.deck[first];
+            self.deal(&mut board, self.known + 1, first + 1, &mut equities);
+        }
+        equities
+    }
+
+    fn deal(&self, board: &mut [Card; 5], at: usize, from: usize, equities: &mut [Equity]) {
+        if at == board.len() {
+            self.showdown(board, equities);
+            return;
+        }
+        for i in from..self.deck.len() {
+            board[at] = self.deck[i];
+            self.deal(board, at + 1, i + 1, equities);
+        }
+    }
+
+    fn monte_carlo(&self, iterations: u32, seed: u64) -> Vec<Equity> {
+        let chunks = (0..iterations.div_ceil(CHUNK))
+            .map(|chunk| self.monte_carlo_chunk(seed, chunk, iterations));
+        chunks.fold(self.none(), add_all)
+    }
+
+    // block `chunk` of a run of `iterations`, from its own random stream
+    fn monte_carlo_chunk(&self, seed: u64, chunk: u32, iterations: u32) -> Vec<Equity> {
+        let mut rng = SplitMix::new(seed, chunk);
+        let mut deck = self.deck.clone();
+        let mut equities = self.none();
+        let count = CHUNK.min(iterations - chunk * CHUNK);
+        for _ in 0..count {
+            self.random_showdown(&mut deck, &mut rng, &mut equities);
+        }
+        equities
+    }
+
+    // the showdown on one random runout dealt from `deck`, a permutation
+    // of `self.deck`
+    fn random_showdown(&self, deck: &mut [Card], rng: &mut SplitMix, equities: &mut [Equity]) {
+        let mut board = self.board;
+        // a partial Fisher-Yates shuffle puts the new cards up front
+        for (i, slot) in board[self.known..].iter_mut().enumerate() {
+            let j = i + rng.below(deck.len() - i);
+            deck.swap(i, j);
+            *slot = deck[i];
+        }
+        self.showdown(&board, equities);
+    }
+}
+
+/// SplitMix64, small and fast, with independent streams per seed.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn new(seed: u64, stream: u32) -> Self {
+        let mut mixer = SplitMix(seed);
+        SplitMix(mixer.next() ^ u64::from(stream).wrapping_mul(0xd1b5_4a32_d192_ed03))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in 0..n
+    fn below(&mut self, n: usize) -> usize {
+        (((self.next() >> 32) * n as u64) >> 32) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards<const N: usize>(s: &str) -> [Card; N] {
+        Card::parse_many(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_equity() {
+        let board: [Card; 3] = cards("Kh 7d 2c");
+        let exact = equity_exhaustive(cards("As Ad"), cards("Ks Qs"), &board).unwrap();
+        assert_eq!(exact.total(), 990);
+        assert!(exact.equity() > 0.8);
+
+        let sampled = equity_monte_carlo(cards("As Ad"), cards("Ks Qs"), &board, 10_000, 1);
+        let sampled = sampled.unwrap();
+        assert_eq!(sampled.total(), 10_000);
+        assert!((sampled.equity() - exact.equity()).abs() < 0.02);
+        let again = equity_monte_carlo(cards("As Ad"), cards("Ks Qs"), &board, 10_000, 1);
+        assert_eq!(again.unwrap(), sampled);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let aces = equity_monte_carlo(cards("As Ah"), cards("Kd Kc"), &[], 50_000, 42).unwrap();
+        assert_eq!(aces.total(), 50_000);
+        assert!((aces.equity() - 0.82).abs() < 0.015, "{:?}", aces);
+        assert!(aces.ties > 0);
+        assert_eq!(aces.win() + aces.tie() + aces.loss(), 1.0);
+
+        // a royal flush made on the turn
+        let board: [Card; 4] = cards("Qs Js 10s 2d");
+        let nuts = equity_monte_carlo(cards("As Ks"), cards("Ad Kd"), &board, 1000, 3).unwrap();
+        assert_eq!((nuts.wins, nuts.ties, nuts.losses), (1000, 0, 0));
+        assert_eq!(nuts.equity(), 1.0);
+
+        let other_seed = equity_monte_carlo(cards("As Ah"), cards("Kd Kc"), &[], 50_000, 43);
+        assert_ne!(other_seed.unwrap(), aces);
+        let none = equity_monte_carlo(cards("As Ah"), cards("Kd Kc"), &[], 0, 42).unwrap();
+        assert_eq!((none.total(), none.equity()), (0, 0.0));
+
+        let short: [Card; 2] = cards("2c 3c");
+        let err = equity_monte_carlo(cards("As Ah"), cards("Kd Kc"), &short, 10, 1);
+        assert_eq!(
+            err,
+            Err(Error::BadCard("invalid board of 2 cards".to_string()))
+        );
+        let err = equity_monte_carlo(cards("As Ah"), cards("Ks As"), &[], 10, 1);
+        assert_eq!(err, Err(Error::DuplicateCard(cards::<1>("As")[0])));
+    }
+
+    // the same counts from `HoldemHand`, one runout at a time
+    fn brute_force(hero: [Card; 2], villain: [Card; 2], board: &[Card]) -> Equity {
+        let dead: CardSet = [&hero[..], &villain[..], board]
+            .concat()
+            .into_iter()
+            .collect();
+        let deck: Vec<Card> = (CardSet::full() - dead).iter().collect();
+        let mut runouts: Vec<Vec<Card>> = vec![board.to_vec()];
+        for _ in board.len()..5 {
+            runouts = runouts
+                .into_iter()
+                .flat_map(|runout| {
+                    let last = runout[board.len()..].last().copied();
+                    let next = deck.iter().filter(move |&&c| last.is_none_or(|l| c > l));
+                    next.map(move |&c| [&runout[..], &[c]].concat())
+                })
+                .collect();
+        }
+        let mut equity = Equity::default();
+        for runout in runouts {
+            let best = |hole: [Card; 2]| {
+                let seven = [&hole[..], &runout].concat().try_into().unwrap();
+                crate::HoldemHand::best_of_seven(seven).unwrap()
+            };
+            match best(hero).cmp(&best(villain)) {
+                Ordering::Greater => equity.wins += 1,
+                Ordering::Equal => equity.record_tie(2),
+                Ordering::Less => equity.losses += 1,
+            }
+        }
+        equity
+    }
+
+    #[test]
+    fn test_exhaustive() {
+        let (hero, villain) = (cards("As Ks"), cards("Qh Qd"));
+        let preflop = equity_exhaustive(hero, villain, &[]).unwrap();
+        assert_eq!(preflop.total(), 1_712_304);
+        assert_eq!((preflop.wins, preflop.ties), (787_966, 6_732));
+        assert!((preflop.equity() - 0.4621).abs() < 0.0001);
+
+        let flop: [Card; 3] = cards("Js 7s 2c");
+        let flopped = equity_exhaustive(hero, villain, &flop).unwrap();
+        assert_eq!((flopped.wins, flopped.ties, flopped.losses), (539, 0, 451));
+        assert_eq!(flopped, brute_force(hero, villain, &flop));
+
+        for turn in ["Js 7s 2c 3h", "Jd 10c 2s 9h", "5c 4d 3h Qs"] {
+            let turn: [Card; 4] = cards(turn);
+            let equity = equity_exhaustive(hero, villain, &turn).unwrap();
+            assert_eq!(equity.total(), 44);
+            assert_eq!(equity, brute_force(hero, villain, &turn));
+        }
+
+        // chopped on the river with the board playing
+        let river: [Card; 5] = cards("2c 3d 4h 5s 6c");
+        let chop = equity_exhaustive(hero, villain, &river).unwrap();
+        assert_eq!((chop.ties, chop.total()), (1, 1));
+
+        let err = equity_exhaustive(hero, villain, &cards::<3>("Js 7s Qd"));
+        assert_eq!(err, Err(Error::DuplicateCard(cards::<1>("Qd")[0])));
+    }
+
+    fn range(combos: &[&str]) -> Range {
+        combos.iter().map(|s| cards::<2>(s)).collect()
+    }
+
+    #[test]
+    fn test_vs_range() {
+        let aces = range(&["As Ah", "As Ad", "As Ac", "Ah Ad", "Ah Ac", "Ad Ac"]);
+        let kings_queens = range(&[
+            "Ks Kh", "Ks Kd", "Ks Kc", "Kh Kd", "Kh Kc", "Kd Kc", "Qs Qh", "Qs Qd", "Qs Qc",
+            "Qh Qd", "Qh Qc", "Qd Qc",
+        ]);
+        let method = EquityMethod::MonteCarlo {
+            iterations: 40_000,
+            seed: 5,
+        };
+        let result = equity_vs_range(&aces, &kings_queens, &[], method).unwrap();
+        assert_eq!(result.overall.total(), 40_000);
+        assert_eq!(result.combos.len(), 6);
+        assert!(
+            (result.equity() - 0.816).abs() < 0.015,
+            "{}",
+            result.equity()
+        );
+        assert!(result
+            .combos
+            .iter()
+            .all(|(_, e)| (e.equity() - 0.816).abs() < 0.05));
+
+        // exhaustive on a flop is the sum of the matchups, each weighing the same
+        let board: [Card; 3] = cards("Kc 8h 3d");
+        let result = equity_vs_range(&aces, &kings_queens, &board, EquityMethod::Exhaustive);
+        let result = result.unwrap();
+        let mut expected = Equity::default();
+        for h in aces.iter() {
+            for v in kings_queens.iter().filter(|v| !v.contains(&board[0])) {
+                expected += equity_exhaustive(h, v, &board).unwrap();
+            }
+        }
+        assert_eq!(result.overall, expected);
+        assert_eq!(result.overall.total(), 6 * 9 * 990);
+    }
+
+    #[test]
+    fn test_vs_range_blockers() {
+        let aces = range(&["As Ah", "As Ad", "As Ac", "Ah Ad", "Ah Ac", "Ad Ac"]);
+        let hero = Range::from(cards::<2>("As Kd"));
+        let board: [Card; 3] = cards("Qc 7h 2s");
+        let result = equity_vs_range(&hero, &aces, &board, EquityMethod::Exhaustive).unwrap();
+        // only the three aces without the As remain
+        assert_eq!(result.overall.total(), 3 * 990);
+        assert_eq!(result.combos, [(cards("As Kd"), result.overall)]);
+
+        // a hero combo blocked by the board drops out
+        let hero = range(&["Qc Qd", "Kh Kc"]);
+        let result = equity_vs_range(&hero, &aces, &board, EquityMethod::Exhaustive).unwrap();
+        assert_eq!(result.combos.len(), 1);
+        assert_eq!(result.combos[0].0, cards("Kc Kh"));
+
+        let (hero, villain) = (Range::from(cards::<2>("As Ah")), range(&["As Kd", "Ah Kd"]));
+        let blocked = equity_vs_range(&hero, &villain, &board, EquityMethod::Exhaustive);
+        assert_eq!(blocked.unwrap(), RangeEquity::default());
+    }
+
+    #[test]
+    fn test_vs_range_weights() {
+        let board: [Card; 3] = cards("2c 7d 9h");
+        let kings = Range::parse("KK").unwrap();
+        let versus = |hero: &str, method| {
+            let hero = Range::parse(hero).unwrap();
+            equity_vs_range(&hero, &kings, &board, method).unwrap()
+        };
+        let exhaustive = |hero| versus(hero, EquityMethod::Exhaustive).equity();
+        let (aces, queens) = (exhaustive("AA"), exhaustive("QQ"));
+        let (none, half, full) = (
+            exhaustive("AA@0, QQ"),
+            exhaustive("AA@50, QQ"),
+            exhaustive("AA, QQ"),
+        );
+        assert_eq!(none, queens);
+        assert!((full - (aces + queens) / 2.0).abs() < 1e-12);
+        assert!(none < half && half < full);
+        // 36 pairs of combos each for the aces and the queens, every pair
+        // with the same 990 runouts
+        let expected = (0.5 * aces + queens) / 1.5;
+        assert!((half - expected).abs() < 1e-12, "{} {}", half, expected);
+        // the runouts themselves are not weighted
+        let result = versus("AA@50, QQ", EquityMethod::Exhaustive);
+        assert_eq!(result.overall.equity(), full);
+
+        let method = EquityMethod::MonteCarlo {
+            iterations: 40_000,
+            seed: 9,
+        };
+        let sampled = versus("AA@50, QQ", method).equity();
+        assert!(
+            (sampled - expected).abs() < 0.01,
+            "{} {}",
+            sampled,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_by_street() {
+        // a flopped set against an overpair, the overpair rivering nothing
+        let (hero, villain) = (cards("7s 7h"), cards("Ks Kd"));
+        let board: [Card; 5] = cards("7d 2c 9h Jc 3s");
+        let [preflop, flop, turn, river] = equity_by_street(hero, villain, board).unwrap();
+        assert_eq!(preflop.total(), 1_712_304);
+        assert!(preflop.equity() < 0.2);
+        assert!(flop.equity() > 0.9);
+        assert_eq!(flop.total(), 990);
+        // the two kings left
+        assert_eq!(turn.losses, 2);
+        assert_eq!(turn.total(), 44);
+        assert_eq!((river.wins, river.total(), river.equity()), (1, 1, 1.0));
+
+        let result = crate::showdown(&[hero, villain], board).unwrap();
+        assert_eq!(result.winners, [0]);
+        let chop: [Card; 5] = cards("Ac Qc Jd 10h 8s");
+        let [.., river] = equity_by_street(cards("Ah Kh"), cards("Ad Kd"), chop).unwrap();
+        assert_eq!((river.ties, river.equity()), (1, 0.5));
+    }
+
+    #[test]
+    fn test_river_distribution() {
+        // nine hearts for the nut flush draw against top pair, and three
+        // aces for a bigger pair
+        let (hero, villain) = (cards("Ah 5h"), cards("Kc Qd"));
+        let board: [Card; 4] = cards("Kh 9h 2c 7s");
+        let rivers = river_distribution(hero, villain, board).unwrap();
+        assert_eq!(rivers.len(), 44);
+        let dead: Vec<Card> = hero.into_iter().chain(villain).chain(board).collect();
+        assert!(rivers.iter().all(|(river, _)| !dead.contains(river)));
+        let wins: Vec<Card> = rivers
+            .iter()
+            .filter(|(_, outcome)| *outcome == Outcome::Win)
+            .map(|&(river, _)| river)
+            .collect();
+        assert_eq!(
+            wins,
+            Card::parse_many("2h 3h 4h 6h 7h 8h Th Jh Qh Ad Ac As").unwrap()
+        );
+        let summary: Equity = rivers.iter().map(|&(_, outcome)| outcome).collect();
+        assert_eq!(summary, equity_exhaustive(hero, villain, &board).unwrap());
+
+        // a wheel draw against the same wheel draw chops every river
+        let chops = river_distribution(cards("Ac 5d"), cards("As 5s"), cards("2c 3d 4h Kd"));
+        let chops: Equity = chops.unwrap().into_iter().map(|(_, o)| o).collect();
+        assert_eq!((chops.ties, chops.total(), chops.equity()), (44, 44, 0.5));
+
+        // one villain combo gives the same rivers; its own cards can't fall
+        let single = Range::from(villain);
+        let equities = river_distribution_vs_range(hero, &single, board).unwrap();
+        let expected: Vec<(Card, f64)> = rivers
+            .iter()
+            .map(|&(river, outcome)| (river, if outcome == Outcome::Win { 1.0 } else { 0.0 }))
+            .collect();
+        assert_eq!(equities, expected);
+
+        // the hero's aces leave KK and AA, which the river flush beats
+        let range = Range::parse("AA, KK").unwrap();
+        let equities = river_distribution_vs_range(hero, &range, board).unwrap();
+        assert_eq!(equities.len(), 46);
+        let of = |s: &str| {
+            equities
+                .iter()
+                .find(|(c, _)| *c == cards::<1>(s)[0])
+                .unwrap()
+                .1
+        };
+        assert_eq!(of("Qh"), 1.0);
+        assert_eq!(of("2d"), 0.0);
+        let err = river_distribution_vs_range(hero, &range, cards("Kh 9h 2c Ah"));
+        assert!(matches!(err, Err(Error::DuplicateCard(_))));
+    }
+
+    #[test]
+    fn test_ehs() {
+        let villain = Range::parse("22+, A2s+, K9s+, QTs+, JTs, ATo+, KJo+").unwrap();
+        let split = |s: &str| {
+            let cards = Card::parse_many(s).unwrap();
+            ([cards[0], cards[1]], cards[2..].to_vec())
+        };
+
+        // the nut flush on the river, nothing left to come
+        let (hole, board) = split("As Qs Ks 9s 4s 2d 7h");
+        let river = ehs(hole, &board, &villain).unwrap();
+        assert_eq!((river.hs, river.ppot, river.npot), (1.0, 0.0, 0.0));
+        assert_eq!(river.ehs(), 1.0);
+        let [ahead, tied, behind] = river.now;
+        assert!(ahead > 0.0 && tied == 0.0 && behind == 0.0);
+        assert_eq!(river.table[0][0], ahead);
+
+        // the royal flush on the flop can't be caught
+        let (hole, board) = split("Js Ts As Ks Qs");
+        let royal = ehs(hole, &board, &villain).unwrap();
+        assert_eq!((royal.hs, royal.npot), (1.0, 0.0));
+
+        // a flush draw and open-ended straight draw against a strong range
+        let (hole, board) = split("8h 7h 9h 6h 2c");
+        let draw = ehs(hole, &board, &villain).unwrap();
+        assert!(draw.hs < 0.2, "{}", draw.hs);
+        assert!(draw.ppot > 0.5, "{}", draw.ppot);
+        let turn = ehs_one_street(hole, &board, &villain).unwrap();
+        assert_eq!(turn.hs, draw.hs);
+        assert!(turn.ppot > 0.25 && turn.ppot < draw.ppot, "{}", turn.ppot);
+        assert!(draw.ehs() > draw.hs);
+
+        // every live combo meets each of the C(45, 2) runouts it misses
+        let live = villain.count_combos(&hole.into_iter().chain(board.clone()).collect());
+        let runouts: f64 = draw.table.iter().flatten().sum();
+        assert_eq!(runouts, live * 45.0 * 44.0 / 2.0);
+        assert!(ehs(hole, &board[..2], &villain).is_err());
+    }
+
+    #[test]
+    fn test_multiway() {
+        let players = [cards("As Ah"), cards("Ks Kh"), cards("Qd Qc")];
+        let exact = equity_multiway(&players, &[], EquityMethod::Exhaustive).unwrap();
+        let shares: Vec<f64> = exact.iter().map(Equity::equity).collect();
+        assert!((shares.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        for (share, expected) in shares.iter().zip([0.665, 0.167, 0.168]) {
+            assert!((share - expected).abs() < 0.001, "{:?}", shares);
+        }
+        // three-way chops show up for every player alike
+        assert!(exact
+            .iter()
+            .all(|e| e.ties == exact[0].ties && e.total() == 1_370_754));
+
+        let method = EquityMethod::MonteCarlo {
+            iterations: 30_000,
+            seed: 8,
+        };
+        let sampled = equity_multiway(&players, &[], method).unwrap();
+        let sum: f64 = sampled.iter().map(Equity::equity).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+        for (e, share) in sampled.iter().zip(&shares) {
+            assert!((e.equity() - share).abs() < 0.015);
+        }
+
+        // heads-up agrees with `equity_exhaustive`
+        let board: [Card; 3] = cards("Kc 8h 3d");
+        let heads_up = equity_multiway(&players[1..], &board, EquityMethod::Exhaustive).unwrap();
+        assert_eq!(
+            heads_up[0],
+            equity_exhaustive(players[1], players[2], &board).unwrap()
+        );
+
+        let err = equity_multiway(
+            &[cards("As Ah"), cards("Ks Kh"), cards("Kh Qc")],
+            &[],
+            method,
+        );
+        assert_eq!(err, Err(Error::DuplicateCard(cards::<1>("Kh")[0])));
+        let err = equity_multiway(&players[..1], &[], method);
+        assert_eq!(
+            err,
+            Err(Error::BadCard("invalid number of players: 1".to_string()))
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel() {
+        let (hero, villain) = (cards("Ah Kh"), cards("Qs Qd"));
+        for board in [&cards::<3>("Jh 7h 2c")[..], &cards::<4>("Jh 7h 2c 9s")[..]] {
+            let serial = equity_exhaustive(hero, villain, board).unwrap();
+            assert_eq!(equity_exhaustive_par(hero, villain, board).unwrap(), serial);
+        }
+        let serial = equity_monte_carlo(hero, villain, &[], 20_000, 9).unwrap();
+        let parallel = equity_monte_carlo_par(hero, villain, &[], 20_000, 9).unwrap();
+        assert_eq!(parallel, serial);
+    }
+}
@@ -1,13 +1,22 @@
 use thiserror::Error;
 
+use crate::card::Card;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Error {
     #[error("Bad value: {0}")]
     BadValue(String),
 
+    #[error("Bad value index: {0}")]
+    BadValueIndex(u8),
+
     #[error("Bad suit: {0}")]
     BadSuit(String),
 
+    #[error("Bad suit index: {0}")]
+    BadSuitIndex(u8),
+
     #[error("Bad card: {0}")]
     BadCard(String),
 
@@ -16,4 +25,25 @@ pub enum Error {
 
     #[error("Bad hand error")]
     BadHand,
+
+    #[error("Duplicate card: {0}")]
+    DuplicateCard(Card),
+
+    #[error("Bad range: {0}")]
+    BadRange(String),
+
+    #[error("Bad lookup table: {0}")]
+    BadTable(String),
+
+    #[error("Bad odds: {0}")]
+    BadOdds(String),
+
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e.to_string())
+    }
 }
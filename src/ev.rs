@@ -0,0 +1,241 @@
+//! The expected value of folding, calling and shoving.
+//!
+//! Amounts are chips, `u64`, in whatever unit the caller counts them:
+//! chips, big blinds times a hundred, cents. Expected values come back as
+//! `f64` in the same unit, measured from the moment of the decision, so
+//! folding is always worth 0. A `pot` counts every chip in the middle,
+//! including a bet being faced.
+
+use crate::equity::Equity;
+
+/// The expected value of calling `to_call` into `pot` with `equity`:
+/// `equity * pot - (1 - equity) * to_call`. It is 0 exactly when `equity`
+/// is `odds::pot_odds(pot, to_call)`.
+///
+/// ```
+/// use poker::ev::ev_call;
+///
+/// // 25% is just enough against a half-pot bet
+/// assert_eq!(ev_call(0.25, 100 + 50, 50), 0.0);
+/// ```
+pub fn ev_call(equity: f64, pot: u64, to_call: u64) -> f64 {
+    equity * pot as f64 - (1.0 - equity) * to_call as f64
+}
+
+/// The expected value of shoving `shove_size` into `pot`, the villain
+/// folding with `fold_probability` or else adding `villain_call_size` and
+/// seeing the board out against a hand with `hero_equity_when_called`.
+/// The call is less than the shove when the villain has a bet in the pot
+/// already. `shove_size` must be the effective amount: against a shorter
+/// stack, only what the villain can match, the rest coming back.
+///
+/// ```
+/// use poker::ev::ev_shove;
+///
+/// // a pot-sized semi-bluff with 35% when called, folding out half
+/// let ev = ev_shove(0.35, 0.5, 100, 100, 100);
+/// assert!((ev - 52.5).abs() < 1e-9);
+/// ```
+pub fn ev_shove(
+    hero_equity_when_called: f64,
+    fold_probability: f64,
+    pot: u64,
+    shove_size: u64,
+    villain_call_size: u64,
+) -> f64 {
+    let called = called_ev(hero_equity_when_called, pot, shove_size, villain_call_size);
+    fold_probability * pot as f64 + (1.0 - fold_probability) * called
+}
+
+/// The fold probability at which `ev_shove` is 0: `shove / (pot + shove)`
+/// for a pure bluff called for the same amount. A shove that profits when
+/// called needs no folds.
+pub fn break_even_fold_probability(
+    hero_equity_when_called: f64,
+    pot: u64,
+    shove_size: u64,
+    villain_call_size: u64,
+) -> f64 {
+    let called = called_ev(hero_equity_when_called, pot, shove_size, villain_call_size);
+    if called >= 0.0 {
+        0.0
+    } else {
+        -called / (pot as f64 - called)
+    }
+}
+
+// the shove's expected value when it is called
+fn called_ev(equity: f64, pot: u64, shove_size: u64, villain_call_size: u64) -> f64 {
+    let final_pot = pot as f64 + shove_size as f64 + villain_call_size as f64;
+    equity * final_pot - shove_size as f64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Fold,
+    Call,
+    Shove,
+}
+
+/// The expected values of the actions open in one spot, folding among
+/// them.
+///
+/// ```
+/// use poker::{equity::Equity, ev::{Action, Decision}};
+///
+/// let equity = Equity { wins: 1, losses: 1, ..Default::default() };
+/// let decision = Decision::new(150).call(&equity, 50).shove(&equity, 0.5, 200, 150);
+/// assert_eq!(decision.ev(Action::Call), Some(50.0));
+/// assert_eq!(decision.best(), (Action::Shove, 100.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pot: u64,
+    evs: Vec<(Action, f64)>,
+}
+
+impl Decision {
+    /// A spot with `pot` in the middle and only folding considered yet.
+    pub fn new(pot: u64) -> Decision {
+        Decision {
+            pot,
+            evs: vec![(Action::Fold, 0.0)],
+        }
+    }
+
+    /// Adds calling `to_call`, see `ev_call`.
+    pub fn call(mut self, equity: &Equity, to_call: u64) -> Decision {
+        let ev = ev_call(equity.equity(), self.pot, to_call);
+        self.set(Action::Call, ev);
+        self
+    }
+
+    /// Adds shoving, `equity_when_called` against the hands that call;
+    /// see `ev_shove`.
+    pub fn shove(
+        mut self,
+        equity_when_called: &Equity,
+        fold_probability: f64,
+        shove_size: u64,
+        villain_call_size: u64,
+    ) -> Decision {
+        let ev = ev_shove(
+            equity_when_called.equity(),
+            fold_probability,
+            self.pot,
+            shove_size,
+            villain_call_size,
+        );
+        self.set(Action::Shove, ev);
+        self
+    }
+
+    pub fn ev(&self, action: Action) -> Option<f64> {
+        self.evs
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|&(_, ev)| ev)
+    }
+
+    /// The actions added, folding first.
+    pub fn evs(&self) -> &[(Action, f64)] {
+        &self.evs
+    }
+
+    /// The action with the highest expected value, the passive one on a
+    /// tie.
+    pub fn best(&self) -> (Action, f64) {
+        let passive = |action: Action| action as u8;
+        let best = self
+            .evs
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(passive(b.0).cmp(&passive(a.0))));
+        *best.unwrap_or(&(Action::Fold, 0.0))
+    }
+
+    fn set(&mut self, action: Action, ev: f64) {
+        self.evs.retain(|(a, _)| *a != action);
+        self.evs.push((action, ev));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odds::pot_odds;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_ev_call() {
+        // break even exactly at the pot odds
+        for (pot, to_call) in [(150, 50), (300, 100), (1000, 999), (7, 3)] {
+            assert!(close(ev_call(pot_odds(pot, to_call), pot, to_call), 0.0));
+        }
+        assert_eq!(ev_call(0.5, 200, 100), 50.0);
+        assert_eq!(ev_call(1.0, 200, 100), 200.0);
+        assert_eq!(ev_call(0.0, 200, 100), -100.0);
+    }
+
+    #[test]
+    fn test_ev_shove() {
+        // shoving 100 into 100 with 35% when called: 0.35 * 300 - 100 = 5
+        // called, 100 folded out
+        assert!(close(ev_shove(0.35, 0.5, 100, 100, 100), 52.5));
+        assert!(close(ev_shove(0.35, 0.0, 100, 100, 100), 5.0));
+        assert!(close(ev_shove(0.35, 1.0, 100, 100, 100), 100.0));
+
+        // a pure bluff needs shove / (pot + shove) folds
+        let needed = break_even_fold_probability(0.0, 100, 100, 100);
+        assert!(close(needed, 0.5));
+        let needed = break_even_fold_probability(0.0, 150, 300, 300);
+        assert!(close(needed, 300.0 / 450.0));
+        assert!(close(ev_shove(0.0, needed, 150, 300, 300), 0.0));
+        // a semi-bluff needs fewer, and a shove ahead when called none
+        let needed = break_even_fold_probability(0.25, 100, 200, 200);
+        assert!(needed > 0.0 && needed < 200.0 / 300.0);
+        assert!(close(ev_shove(0.25, needed, 100, 200, 200), 0.0));
+        assert_eq!(break_even_fold_probability(0.6, 100, 200, 200), 0.0);
+
+        // raising all in over a bet of 450 into 100 leaves 50 to call
+        assert!(close(
+            ev_shove(0.5, 0.0, 550, 500, 50),
+            0.5 * 1100.0 - 500.0
+        ));
+        // shoving 1000 at a villain with 100 left risks only 100
+        let (shove, villain_stack) = (1000u64, 100);
+        let effective = shove.min(villain_stack);
+        let ev = ev_shove(0.5, 0.0, 100, effective, villain_stack);
+        assert!(close(ev, 0.5 * 300.0 - 100.0));
+    }
+
+    #[test]
+    fn test_decision() {
+        let equity = |wins, losses| Equity {
+            wins,
+            losses,
+            ..Default::default()
+        };
+        let decision = Decision::new(150).call(&equity(20, 80), 50);
+        assert_eq!(decision.ev(Action::Call), Some(-10.0));
+        assert_eq!(decision.ev(Action::Shove), None);
+        assert_eq!(decision.best(), (Action::Fold, 0.0));
+
+        let decision = decision.shove(&equity(35, 65), 0.5, 100, 100);
+        assert!(close(
+            decision.ev(Action::Shove).unwrap(),
+            0.5 * 150.0 + 0.5 * (0.35 * 350.0 - 100.0)
+        ));
+        assert_eq!(decision.best().0, Action::Shove);
+        let actions: Vec<Action> = decision.evs().iter().map(|&(a, _)| a).collect();
+        assert_eq!(actions, [Action::Fold, Action::Call, Action::Shove]);
+
+        // calling at exactly the pot odds ties folding
+        let decision = Decision::new(150).call(&equity(1, 3), 50);
+        assert_eq!(decision.best(), (Action::Fold, 0.0));
+        let decision = Decision::new(150).call(&equity(1, 1), 50);
+        assert_eq!(decision.best(), (Action::Call, 50.0));
+    }
+}
@@ -0,0 +1,247 @@
+//! Table-driven hand evaluation for hot loops.
+//!
+//! `eval5` gives the same answer as `HoldemHand::rank`, as a class index
+//! (see `Rank::class_index`), from a handful of lookups: flushes and
+//! hands of five distinct values are looked up by their value bits, the
+//! rest by the product of one prime per value. `eval7` takes the best
+//! of the 21 five-card hands in seven cards, and the batch functions run
+//! many hands through one fetch of the tables.
+
+use std::sync::OnceLock;
+
+use crate::{
+    card::Card,
+    holdem::{Rank, RankCategory},
+};
+
+// one prime per value, Two to Ace, so a product identifies a value multiset
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+// in the bit tables where no class has those value bits
+const EMPTY: u16 = u16::MAX;
+
+struct Tables {
+    // by value bits: all five cards of one suit, and five distinct values offsuit
+    flushes: Vec<u16>,
+    unique: Vec<u16>,
+    // prime product of the values of every other class, sorted by product
+    products: Vec<(u32, u16)>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = Tables {
+            flushes: vec![EMPTY; 1 << 13],
+            unique: vec![EMPTY; 1 << 13],
+            products: Vec::new(),
+        };
+        for (index, (rank, cards)) in Rank::enumerate_classes().enumerate() {
+            let index = index as u16;
+            let (bits, product) = values_key(&cards);
+            match rank.category() {
+                RankCategory::Flush
+                | RankCategory::StraightFlush
+                | RankCategory::RoyalStraightFlush => tables.flushes[bits as usize] = index,
+                RankCategory::HighCard | RankCategory::Straight => {
+                    tables.unique[bits as usize] = index
+                }
+                _ => tables.products.push((product, index)),
+            }
+        }
+        tables.products.sort_unstable();
+        tables
+    })
+}
+
+// a bit per value present and the product of the values' primes
+fn values_key(cards: &[Card; 5]) -> (u16, u32) {
+    let mut bits = 0;
+    let mut product = 1;
+    for card in cards {
+        let value = card.value() as usize - 2;
+        bits |= 1 << value;
+        product *= PRIMES[value];
+    }
+    (bits, product)
+}
+
+/// The class index of five distinct cards: 0 for a royal flush up to
+/// 7461 for 7-5-4-3-2 offsuit, matching `rank().class_index()`. Use
+/// `Rank::from_class_index` to get the `Rank` back.
+///
+/// Cards are not checked for duplicates; with a repeated card the result
+/// is meaningless, though it never panics.
+///
+/// ```
+/// use poker::{fast::eval5, HoldemHand, Rank};
+///
+/// let hand: HoldemHand = "Kh Kd Kc 10s 10h".parse().unwrap();
+/// let index = eval5(hand.cards());
+/// assert_eq!(index, hand.rank().class_index());
+/// assert_eq!(Rank::from_class_index(index), Some(hand.rank()));
+/// ```
+pub fn eval5(cards: &[Card; 5]) -> u16 {
+    eval5_with(tables(), cards)
+}
+
+/// The class index of the best five of seven distinct cards, as `eval5`
+/// numbers them.
+pub fn eval7(cards: &[Card; 7]) -> u16 {
+    eval7_with(tables(), cards)
+}
+
+/// Appends the `eval5` of each hand to `out`.
+pub fn eval_batch5(hands: &[[Card; 5]], out: &mut Vec<u16>) {
+    let tables = tables();
+    out.reserve(hands.len());
+    out.extend(hands.iter().map(|cards| eval5_with(tables, cards)));
+}
+
+/// Appends the `eval7` of each hand to `out`.
+///
+/// ```
+/// use poker::{card::Card, fast};
+///
+/// let hands: Vec<[Card; 7]> = ["As Ks Qs Js 10s 2c 2d", "7h 5d 4c 3s 2h 9c 9d"]
+///     .iter()
+///     .map(|s| Card::parse_many(s).unwrap().try_into().unwrap())
+///     .collect();
+/// let mut out = Vec::new();
+/// fast::eval_batch(&hands, &mut out);
+/// assert_eq!(out, [fast::eval7(&hands[0]), fast::eval7(&hands[1])]);
+/// assert_eq!(out[0], 0);
+/// ```
+pub fn eval_batch(hands: &[[Card; 7]], out: &mut Vec<u16>) {
+    let tables = tables();
+    out.reserve(hands.len());
+    out.extend(hands.iter().map(|cards| eval7_with(tables, cards)));
+}
+
+/// The `eval7` of each hand, lazily.
+pub fn evaluate_iter<I>(hands: I) -> impl Iterator<Item = u16>
+where
+    I: IntoIterator<Item = [Card; 7]>,
+{
+    let tables = tables();
+    hands.into_iter().map(|cards| eval7_with(tables, &cards))
+}
+
+fn eval7_with(tables: &Tables, cards: &[Card; 7]) -> u16 {
+    let mut best = u16::MAX;
+    for skip_a in 0..7 {
+        for skip_b in skip_a + 1..7 {
+            let mut hand = [cards[0]; 5];
+            let kept = (0..7).filter(|&i| i != skip_a && i != skip_b);
+            for (slot, i) in hand.iter_mut().zip(kept) {
+                *slot = cards[i];
+            }
+            best = best.min(eval5_with(tables, &hand));
+        }
+    }
+    best
+}
+
+fn eval5_with(tables: &Tables, cards: &[Card; 5]) -> u16 {
+    let (bits, product) = values_key(cards);
+    let suit = cards[0].suit();
+    if cards.iter().all(|card| card.suit() == suit) {
+        return tables.flushes[bits as usize];
+    }
+    if bits.count_ones() == 5 {
+        return tables.unique[bits as usize];
+    }
+    match tables.products.binary_search_by_key(&product, |&(p, _)| p) {
+        Ok(i) => tables.products[i].1,
+        Err(_) => EMPTY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card::sample_deals, HoldemHand};
+
+    fn check(cards: [Card; 5]) {
+        let hand = HoldemHand::new(cards).unwrap();
+        assert_eq!(eval5(&cards), hand.rank().class_index(), "{}", hand);
+    }
+
+    #[test]
+    fn test_eval5() {
+        let tables = tables();
+        assert_eq!(tables.flushes.iter().filter(|&&i| i != EMPTY).count(), 1287);
+        assert_eq!(tables.unique.iter().filter(|&&i| i != EMPTY).count(), 1287);
+        assert_eq!(tables.products.len(), 7462 - 2 * 1287);
+
+        for (_, cards) in Rank::enumerate_classes() {
+            check(cards);
+        }
+        for cards in sample_deals(7, 50_000) {
+            check(cards);
+        }
+        let royal = "As Ks Qs Js 10s".parse::<HoldemHand>().unwrap();
+        assert_eq!(eval5(royal.cards()), 0);
+    }
+
+    #[test]
+    fn test_batch() {
+        let hands = sample_deals::<7>(3, 3000);
+        let mut out = vec![42];
+        eval_batch(&hands, &mut out);
+        assert_eq!(out.len(), hands.len() + 1);
+        assert_eq!(out[0], 42);
+        let iter: Vec<u16> = evaluate_iter(hands.iter().copied()).collect();
+        assert_eq!(out[1..], iter);
+        for (cards, &index) in hands.iter().zip(&out[1..]) {
+            assert_eq!(eval7(cards), index);
+            let best = HoldemHand::best_of_seven(*cards).unwrap();
+            assert_eq!(index, best.rank().class_index());
+        }
+
+        let hands = sample_deals::<5>(4, 3000);
+        let mut out = Vec::new();
+        eval_batch5(&hands, &mut out);
+        let single: Vec<u16> = hands.iter().map(eval5).collect();
+        assert_eq!(out, single);
+    }
+
+    // cargo test --release bench_batch -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_batch() {
+        use std::{hint::black_box, time::Instant};
+        let hands = sample_deals::<7>(5, 100_000);
+        let start = Instant::now();
+        for cards in &hands {
+            black_box(HoldemHand::best_of_seven(black_box(*cards)).unwrap().rank());
+        }
+        let by_hand = start.elapsed();
+        let start = Instant::now();
+        let mut out = Vec::new();
+        eval_batch(black_box(&hands), &mut out);
+        let batch = start.elapsed();
+        println!("best_of_seven: {:?}, eval_batch: {:?}", by_hand, batch);
+    }
+
+    // cargo test --release test_eval5_all_hands -- --ignored
+    #[test]
+    #[ignore]
+    fn test_eval5_all_hands() {
+        let deck = Card::ALL;
+        let mut count = 0;
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            check([deck[a], deck[b], deck[c], deck[d], deck[e]]);
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(count, 2_598_960);
+    }
+}
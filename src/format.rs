@@ -0,0 +1,180 @@
+use crate::{
+    card::{Card, Value},
+    error::Error,
+};
+
+/// Conventions for writing and reading cards.
+///
+/// `CardFormat::default()` matches `Card`'s `Display` and `TryFrom<&str>`:
+/// `10h`, `As`, lenient parsing. In strict mode a card only parses if it is
+/// written exactly as this format would write it.
+///
+/// ```
+/// use poker::{card::Card, format::CardFormat};
+///
+/// let format = CardFormat::default().ten_as_t(true).suit_symbols(true);
+/// let card = Card::try_from("10s").unwrap();
+/// assert_eq!(format.format_card(&card), "T♠");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardFormat {
+    ten_as_t: bool,
+    lowercase_values: bool,
+    uppercase_suits: bool,
+    suit_symbols: bool,
+    strict: bool,
+    separator: String,
+}
+
+impl Default for CardFormat {
+    fn default() -> Self {
+        Self {
+            ten_as_t: false,
+            lowercase_values: false,
+            uppercase_suits: false,
+            suit_symbols: false,
+            strict: false,
+            separator: " ".to_string(),
+        }
+    }
+}
+
+impl CardFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write ten as `T` instead of `10`.
+    pub fn ten_as_t(mut self, yes: bool) -> Self {
+        self.ten_as_t = yes;
+        self
+    }
+
+    /// Write `a`, `k`, `t` instead of `A`, `K`, `T`.
+    pub fn lowercase_values(mut self, yes: bool) -> Self {
+        self.lowercase_values = yes;
+        self
+    }
+
+    /// Write `H`, `S` instead of `h`, `s`. Ignored with `suit_symbols`.
+    pub fn uppercase_suits(mut self, yes: bool) -> Self {
+        self.uppercase_suits = yes;
+        self
+    }
+
+    /// Write suits as `♥ ♦ ♣ ♠`.
+    pub fn suit_symbols(mut self, yes: bool) -> Self {
+        self.suit_symbols = yes;
+        self
+    }
+
+    /// Only accept cards written exactly in this format.
+    pub fn strict(mut self, yes: bool) -> Self {
+        self.strict = yes;
+        self
+    }
+
+    /// Separator used by `format_cards`, a space by default.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    pub fn format_card(&self, card: &Card) -> String {
+        let mut s = match card.value() {
+            Value::Ten if !self.ten_as_t => "10".to_string(),
+            value => value.to_char().to_string(),
+        };
+        if self.lowercase_values {
+            s.make_ascii_lowercase();
+        }
+        let suit = card.suit();
+        match (self.suit_symbols, self.uppercase_suits) {
+            (true, _) => s.push(suit.symbol()),
+            (false, true) => s.push(suit.to_char().to_ascii_uppercase()),
+            (false, false) => s.push(suit.to_char()),
+        }
+        s
+    }
+
+    pub fn format_cards(&self, cards: &[Card]) -> String {
+        cards
+            .iter()
+            .map(|c| self.format_card(c))
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+
+    pub fn parse_card(&self, s: &str) -> Result<Card, Error> {
+        let card = Card::try_from(s)?;
+        if self.strict && self.format_card(&card) != s {
+            return Err(Error::BadCard(s.to_string()));
+        }
+        Ok(card)
+    }
+
+    /// Lenient mode accepts anything `Card::parse_many` does; strict mode
+    /// wants whitespace/comma separated cards in this format.
+    pub fn parse_cards(&self, s: &str) -> Result<Vec<Card>, Error> {
+        if !self.strict {
+            return Card::parse_many(s);
+        }
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .map(|t| self.parse_card(t))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let cards = Card::parse_many("As 10h Kd 2c").unwrap();
+        let default = CardFormat::default();
+        assert_eq!(default.format_cards(&cards), "As 10h Kd 2c");
+        for card in Card::ALL {
+            assert_eq!(default.format_card(&card), card.to_string());
+        }
+
+        let compact = CardFormat::new().ten_as_t(true).separator("");
+        assert_eq!(compact.format_cards(&cards), "AsThKd2c");
+
+        let symbols = CardFormat::new().suit_symbols(true).lowercase_values(true);
+        assert_eq!(symbols.format_cards(&cards), "a♠ 10♥ k♦ 2♣");
+
+        let upper = CardFormat::new().ten_as_t(true).uppercase_suits(true);
+        assert_eq!(upper.format_cards(&cards), "AS TH KD 2C");
+    }
+
+    #[test]
+    fn test_parse() {
+        let lenient = CardFormat::new().ten_as_t(true);
+        let strict = lenient.clone().strict(true);
+        let ten = Card::try_from("Th").unwrap();
+
+        for s in ["Th", "10h", "tH", "T♥"] {
+            assert_eq!(lenient.parse_card(s), Ok(ten));
+        }
+        assert_eq!(strict.parse_card("Th"), Ok(ten));
+        assert_eq!(
+            strict.parse_card("10h"),
+            Err(Error::BadCard("10h".to_string()))
+        );
+        assert_eq!(
+            strict.parse_card("th"),
+            Err(Error::BadCard("th".to_string()))
+        );
+        assert_eq!(
+            strict.parse_card("Tx"),
+            Err(Error::BadSuit("x".to_string()))
+        );
+
+        assert_eq!(lenient.parse_cards("AsTh").map(|c| c.len()), Ok(2));
+        assert_eq!(strict.parse_cards("As, Th").map(|c| c.len()), Ok(2));
+        assert!(strict.parse_cards("AsTh").is_err());
+        assert!(strict.parse_cards("as Th").is_err());
+    }
+}
@@ -1,22 +1,50 @@
-use core::panic;
 use std::{
     array,
+    cmp::Ordering,
     fmt::{Display, Formatter},
+    str::FromStr,
+    sync::OnceLock,
 };
 
 use crate::{
-    card::{Card, Value},
+    card::{find_duplicate, parse_cards, suit_counts, value_counts, Card, CardStyle, Suit, Value},
+    compare::CardsExt,
     error::Error,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct HoldemHand {
+/// Builds a `HoldemHand` from a string literal, panicking with the
+/// parse error (naming the offending token) if it is invalid.
+///
+/// ```
+/// let hand = poker::hand!("As Ks Qs Js 10s");
+/// assert_eq!(hand.rank(), poker::Rank::RoyalStraightFlush);
+/// ```
+#[macro_export]
+macro_rules! hand {
+    ($s:literal) => {
+        $crate::HoldemHand::try_from($s).unwrap_or_else(|e| panic!("invalid hand {:?}: {}", $s, e))
+    };
+}
+
+/// A five-card poker hand and its rank.
+///
+/// Build one with `HoldemHand::new` from five cards, or parse it with
+/// `TryFrom<&str>` / `FromStr`. The cards are kept sorted, highest value
+/// first.
+///
+/// Hands compare by `Rank` alone: two hands that rank the same are equal
+/// even when their suits differ, so two royal flushes tie. Compare
+/// `cards()` to tell identical hands apart.
+#[derive(Debug, Clone, Copy)]
+pub struct HoldemHand {
     cards: [Card; 5],
     rank: Rank,
 }
 
+/// The category of a hand with the values that break ties inside it,
+/// most significant first. Ranks order from weakest to strongest.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Rank {
+pub enum Rank {
     HighCard([Value; 5]),
     Pair([Value; 4]),
     TwoPair([Value; 3]),
@@ -29,110 +57,785 @@ enum Rank {
     RoyalStraightFlush,
 }
 
+/// The kind of hand a `Rank` is, without its tiebreak values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RankCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    Set,
+    Straight,
+    Flush,
+    FullHouse,
+    Bomb,
+    StraightFlush,
+    RoyalStraightFlush,
+}
+
+impl RankCategory {
+    pub const ALL: [RankCategory; 10] = [
+        RankCategory::HighCard,
+        RankCategory::Pair,
+        RankCategory::TwoPair,
+        RankCategory::Set,
+        RankCategory::Straight,
+        RankCategory::Flush,
+        RankCategory::FullHouse,
+        RankCategory::Bomb,
+        RankCategory::StraightFlush,
+        RankCategory::RoyalStraightFlush,
+    ];
+
+    /// A stable identifier like `"three_of_a_kind"`, for logs and storage.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RankCategory::HighCard => "high_card",
+            RankCategory::Pair => "pair",
+            RankCategory::TwoPair => "two_pair",
+            RankCategory::Set => "three_of_a_kind",
+            RankCategory::Straight => "straight",
+            RankCategory::Flush => "flush",
+            RankCategory::FullHouse => "full_house",
+            RankCategory::Bomb => "four_of_a_kind",
+            RankCategory::StraightFlush => "straight_flush",
+            RankCategory::RoyalStraightFlush => "royal_flush",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RankCategory::HighCard => "High Card",
+            RankCategory::Pair => "Pair",
+            RankCategory::TwoPair => "Two Pair",
+            RankCategory::Set => "Three of a Kind",
+            RankCategory::Straight => "Straight",
+            RankCategory::Flush => "Flush",
+            RankCategory::FullHouse => "Full House",
+            RankCategory::Bomb => "Four of a Kind",
+            RankCategory::StraightFlush => "Straight Flush",
+            RankCategory::RoyalStraightFlush => "Royal Flush",
+        }
+    }
+}
+
+impl FromStr for RankCategory {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RankCategory::ALL
+            .into_iter()
+            .find(|c| c.as_str() == s)
+            .ok_or(Error::BadRank)
+    }
+}
+
+impl Rank {
+    pub fn category(&self) -> RankCategory {
+        match self {
+            Rank::HighCard(_) => RankCategory::HighCard,
+            Rank::Pair(_) => RankCategory::Pair,
+            Rank::TwoPair(_) => RankCategory::TwoPair,
+            Rank::Set(_) => RankCategory::Set,
+            Rank::Straight(_) => RankCategory::Straight,
+            Rank::Flush(_) => RankCategory::Flush,
+            Rank::FullHouse(_) => RankCategory::FullHouse,
+            Rank::Bomb(_) => RankCategory::Bomb,
+            Rank::StraightFlush(_) => RankCategory::StraightFlush,
+            Rank::RoyalStraightFlush => RankCategory::RoyalStraightFlush,
+        }
+    }
+
+    pub fn is_at_least(&self, category: RankCategory) -> bool {
+        self.category() >= category
+    }
+
+    /// Builds a rank from its category and payload without checking it
+    /// can occur; `None` if the number of values is wrong.
+    pub fn from_parts(category: RankCategory, values: &[Value]) -> Option<Rank> {
+        fn exact<const N: usize>(values: &[Value]) -> Option<[Value; N]> {
+            values.try_into().ok()
+        }
+        let single = || (values.len() == 1).then(|| values[0]);
+        Some(match category {
+            RankCategory::HighCard => Rank::HighCard(exact(values)?),
+            RankCategory::Pair => Rank::Pair(exact(values)?),
+            RankCategory::TwoPair => Rank::TwoPair(exact(values)?),
+            RankCategory::Set => Rank::Set(exact(values)?),
+            RankCategory::Straight => Rank::Straight(single()?),
+            RankCategory::Flush => Rank::Flush(exact(values)?),
+            RankCategory::FullHouse => Rank::FullHouse(exact(values)?),
+            RankCategory::Bomb => Rank::Bomb(exact(values)?),
+            RankCategory::StraightFlush => Rank::StraightFlush(single()?),
+            RankCategory::RoyalStraightFlush if values.is_empty() => Rank::RoyalStraightFlush,
+            RankCategory::RoyalStraightFlush => return None,
+        })
+    }
+
+    pub(crate) fn values(&self) -> &[Value] {
+        match self {
+            Rank::HighCard(v) | Rank::Flush(v) => v,
+            Rank::Pair(v) => v,
+            Rank::TwoPair(v) | Rank::Set(v) => v,
+            Rank::FullHouse(v) | Rank::Bomb(v) => v,
+            Rank::Straight(v) | Rank::StraightFlush(v) => std::slice::from_ref(v),
+            Rank::RoyalStraightFlush => &[],
+        }
+    }
+
+    // how many of `values` make the hand, the rest are kickers
+    fn made_len(&self) -> usize {
+        match self.category() {
+            RankCategory::TwoPair | RankCategory::FullHouse => 2,
+            RankCategory::Flush => 5,
+            RankCategory::RoyalStraightFlush => 0,
+            _ => 1,
+        }
+    }
+
+    /// The values that make the hand: the top card of a high card hand or
+    /// straight, the paired values, the trips then the pair of a full
+    /// house, all five of a flush and the ace of a royal flush.
+    pub fn primary_values(&self) -> &[Value] {
+        match self {
+            Rank::RoyalStraightFlush => &[Value::Ace],
+            _ => &self.values()[..self.made_len()],
+        }
+    }
+
+    /// The values that only break ties, highest first. Straights, flushes
+    /// and full houses have none, two pair and quads one.
+    pub fn kickers(&self) -> &[Value] {
+        &self.values()[self.made_len()..]
+    }
+
+    /// How many distinct ranks five cards from one deck can have.
+    pub const CLASS_COUNT: u16 = 7462;
+
+    /// Position of this rank among all `CLASS_COUNT` possible ranks,
+    /// strongest first: 0 is a royal flush and 7461 is 7-5-4-3-2 offsuit.
+    /// A rank no hand can have gets the position it would sort into.
+    pub fn class_index(&self) -> u16 {
+        let index = all_classes()
+            .binary_search_by(|(rank, _)| self.cmp(rank))
+            .unwrap_or_else(|i| i);
+        index.min(Self::CLASS_COUNT as usize - 1) as u16
+    }
+
+    /// Strength on a 0 to 100 scale: 100 for a royal flush, 0 for the
+    /// weakest high card.
+    pub fn percentile(&self) -> f64 {
+        let worst = f64::from(Self::CLASS_COUNT - 1);
+        100.0 * (worst - f64::from(self.class_index())) / worst
+    }
+
+    /// The rank at `class_index`, or `None` past the last class.
+    pub fn from_class_index(index: u16) -> Option<Rank> {
+        all_classes().get(index as usize).map(|&(rank, _)| rank)
+    }
+
+    /// Every possible rank with a hand that has it, strongest first. The
+    /// table behind it is built once, from the value multisets rather than
+    /// all 2.6 million hands.
+    pub fn enumerate_classes() -> impl Iterator<Item = (Rank, [Card; 5])> {
+        all_classes().iter().copied()
+    }
+
+    /// Five cards with this rank, or `None` if no hand ranks like this
+    /// (e.g. `Pair([King, King, ..])` or a straight below five high).
+    pub fn representative(&self) -> Option<[Card; 5]> {
+        let straight = |top: Value| -> Option<Vec<Value>> {
+            let values = match top {
+                Value::Five => vec![
+                    Value::Five,
+                    Value::Four,
+                    Value::Three,
+                    Value::Two,
+                    Value::Ace,
+                ],
+                _ => (0..5)
+                    .map(|i| top.checked_sub_rank(i))
+                    .collect::<Option<_>>()?,
+            };
+            Some(values)
+        };
+        let repeat = |counts: &[usize]| -> Vec<Value> {
+            let values = self.values().iter().zip(counts);
+            values
+                .flat_map(|(&v, &n)| std::iter::repeat_n(v, n))
+                .collect()
+        };
+        let values = match self {
+            Rank::HighCard(v) | Rank::Flush(v) => v.to_vec(),
+            Rank::Pair(_) => repeat(&[2, 1, 1, 1]),
+            Rank::TwoPair(_) => repeat(&[2, 2, 1]),
+            Rank::Set(_) => repeat(&[3, 1, 1]),
+            Rank::FullHouse(_) => repeat(&[3, 2]),
+            Rank::Bomb(_) => repeat(&[4, 1]),
+            Rank::Straight(top) | Rank::StraightFlush(top) => straight(*top)?,
+            Rank::RoyalStraightFlush => straight(Value::Ace)?,
+        };
+        let flush = matches!(
+            self.category(),
+            RankCategory::Flush | RankCategory::StraightFlush | RankCategory::RoyalStraightFlush
+        );
+        // copies of a value sit next to each other and so get distinct suits
+        let cards: [Card; 5] = array::from_fn(|i| {
+            let suit = if flush { Suit::Heart } else { Suit::ALL[i % 4] };
+            Card::new(suit, values[i])
+        });
+        match HoldemHand::new(cards) {
+            Ok(hand) if hand.rank == *self => Some(cards),
+            _ => None,
+        }
+    }
+
+    /// Packs the rank into a number that orders the same way: the category
+    /// in bits 20-23, then the values most significant first, four bits
+    /// each from bit 16 down.
+    pub fn to_score(&self) -> u32 {
+        let values = self.values().iter().enumerate();
+        values.fold((self.category() as u32) << 20, |score, (i, v)| {
+            score | (v.value() as u32) << (16 - 4 * i)
+        })
+    }
+
+    pub fn from_score(score: u32) -> Result<Rank, Error> {
+        let value = |i: usize| Value::try_from((score >> (16 - 4 * i) & 0xf) as u8);
+        let values = |n: usize| -> Result<Vec<Value>, Error> {
+            (0..n)
+                .map(value)
+                .collect::<Result<_, _>>()
+                .map_err(|_| Error::BadRank)
+        };
+        let rank = match score >> 20 {
+            0 => Rank::HighCard(array(values(5)?)),
+            1 => Rank::Pair(array(values(4)?)),
+            2 => Rank::TwoPair(array(values(3)?)),
+            3 => Rank::Set(array(values(3)?)),
+            4 => Rank::Straight(values(1)?[0]),
+            5 => Rank::Flush(array(values(5)?)),
+            6 => Rank::FullHouse(array(values(2)?)),
+            7 => Rank::Bomb(array(values(2)?)),
+            8 => Rank::StraightFlush(values(1)?[0]),
+            9 => Rank::RoyalStraightFlush,
+            _ => return Err(Error::BadRank),
+        };
+        // unused low bits must be clear so every rank has one score
+        if rank.to_score() != score {
+            return Err(Error::BadRank);
+        }
+        rank.representative().ok_or(Error::BadRank)?;
+        Ok(rank)
+    }
+}
+
+/// Every rank a hand can have with one such hand, strongest first. Built
+/// once on first use.
+fn all_classes() -> &'static [(Rank, [Card; 5])] {
+    static CLASSES: OnceLock<Vec<(Rank, [Card; 5])>> = OnceLock::new();
+    CLASSES.get_or_init(|| {
+        let mut classes = Vec::with_capacity(Rank::CLASS_COUNT as usize);
+        push_classes(&mut [Value::Ace; 5], 0, Value::ALL.len(), &mut classes);
+        classes.sort_unstable_by_key(|&(rank, _)| std::cmp::Reverse(rank));
+        classes
+    })
+}
+
+// fills values[i..] with non-increasing values below Value::ALL[end] and
+// pushes a hand for every value multiset, plus its flush if it has one
+fn push_classes(
+    values: &mut [Value; 5],
+    i: usize,
+    end: usize,
+    classes: &mut Vec<(Rank, [Card; 5])>,
+) {
+    if i == values.len() {
+        // copies of a value sit next to each other and so get distinct suits;
+        // five of a kind needs a duplicate and is rejected
+        let offsuit = array::from_fn(|i| Card::new(Suit::ALL[i % 4], values[i]));
+        classes.extend(HoldemHand::new(offsuit).map(|hand| (hand.rank, hand.cards)));
+        if values.windows(2).all(|w| w[0] != w[1]) {
+            let suited = values.map(|v| Card::new(Suit::Heart, v));
+            classes.extend(HoldemHand::new(suited).map(|hand| (hand.rank, hand.cards)));
+        }
+        return;
+    }
+    for next in (0..end).rev() {
+        values[i] = Value::ALL[next];
+        push_classes(values, i + 1, next + 1, classes);
+    }
+}
+
+fn array<const N: usize>(values: Vec<Value>) -> [Value; N] {
+    array::from_fn(|i| values[i])
+}
+
+impl crate::poker::Rank for Rank {
+    fn rank(&self) -> u32 {
+        self.to_score()
+    }
+}
+
 impl HoldemHand {
-    fn new(mut cards: [Card; 5]) -> Self {
-        cards.sort_by(|a, b| b.value().cmp(&a.value()));
-        Self {
+    /// Fails with `Error::DuplicateCard` if a card appears twice.
+    pub fn new(cards: [Card; 5]) -> Result<Self, Error> {
+        check_duplicates(&cards)?;
+        Self::from_distinct(cards)
+    }
+
+    fn from_distinct(mut cards: [Card; 5]) -> Result<Self, Error> {
+        cards.sort_desc();
+        Ok(Self {
             cards,
-            rank: Self::rank(&cards),
+            rank: Self::evaluate(&cards)?,
+        })
+    }
+
+    /// The cards, highest value first.
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
+    /// Compares by rank alone, so different suits can tie. Same as `cmp`.
+    pub fn compare(&self, other: &HoldemHand) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+
+    /// Like `compare`, but fails if a card is in both hands.
+    pub fn compare_strict(&self, other: &HoldemHand) -> Result<Ordering, Error> {
+        if let Some(card) = self.cards.iter().find(|c| other.cards.contains(c)) {
+            return Err(Error::DuplicateCard(*card));
+        }
+        Ok(self.compare(other))
+    }
+
+    /// The cards, highest value first.
+    pub fn iter(&self) -> std::iter::Copied<std::slice::Iter<'_, Card>> {
+        self.cards.iter().copied()
+    }
+
+    /// Same as `Rank::kickers`.
+    pub fn kickers(&self) -> &[Value] {
+        self.rank.kickers()
+    }
+
+    /// The best five-card hand out of seven cards.
+    pub fn best_of_seven(cards: [Card; 7]) -> Result<HoldemHand, Error> {
+        Self::best_from(&cards)
+    }
+
+    /// The best five-card hand out of five to seven cards, e.g. the
+    /// current best hand on the turn. Other lengths are `Error::BadHand`.
+    pub fn best_from(cards: &[Card]) -> Result<HoldemHand, Error> {
+        if !(5..=7).contains(&cards.len()) {
+            return Err(Error::BadHand);
+        }
+        check_duplicates(cards)?;
+        Self::best_of(cards, |_| 0)
+    }
+
+    /// `best_of_seven` with the hole cards and the board given apart.
+    pub fn best_of_hole_and_board(hole: [Card; 2], board: [Card; 5]) -> Result<HoldemHand, Error> {
+        Self::best_of_seven(array::from_fn(
+            |i| if i < 2 { hole[i] } else { board[i - 2] },
+        ))
+    }
+
+    /// The best hand out of hole cards and board, along with which hole
+    /// cards it uses. When several combinations rank the same, the one
+    /// using the most hole cards is reported.
+    pub fn best_hand(hole: [Card; 2], board: [Card; 5]) -> Result<BestHand, Error> {
+        let seven: [Card; 7] = array::from_fn(|i| if i < 2 { hole[i] } else { board[i - 2] });
+        check_duplicates(&seven)?;
+        let hand = Self::best_of(&seven, |five| {
+            hole.iter().filter(|c| five.contains(c)).count()
+        })?;
+        let hole_used = hole.map(|c| Some(c).filter(|c| hand.cards.contains(c)));
+        Ok(BestHand { hand, hole_used })
+    }
+
+    // tries every five-card combination of distinct cards, breaking rank
+    // ties by the highest `preference`
+    fn best_of(
+        cards: &[Card],
+        preference: impl Fn(&[Card; 5]) -> usize,
+    ) -> Result<HoldemHand, Error> {
+        let n = cards.len();
+        let mut best: Option<(HoldemHand, usize)> = None;
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                            let key = (Self::from_distinct(five)?, preference(&five));
+                            if best.is_none_or(|best| key > best) {
+                                best = Some(key);
+                            }
+                        }
+                    }
+                }
+            }
         }
+        best.map(|(hand, _)| hand).ok_or(Error::BadHand)
     }
 
-    fn rank(cards: &[Card; 5]) -> Rank {
-        let mut counts = Vec::with_capacity(5);
-        let mut is_flush = true;
+    fn evaluate(cards: &[Card; 5]) -> Result<Rank, Error> {
+        let is_flush = suit_counts(cards).contains(&5);
         let mut is_straight = true;
-        let mut pre = cards[0];
-        counts.push((cards[0].value(), 1));
-        let mut ind = 0;
-        for cur in &cards[1..] {
-            is_flush &= cur.suit() == pre.suit();
+        for pair in cards.windows(2) {
+            let (pre, cur) = (pair[0], pair[1]);
             is_straight &= cur.value() + 1 == pre.value()
                 // "As 5c 4d 3h 2s" is straight
                 || (pre.value() == Value::Ace && cur.value() == Value::Five);
-            if cur.value() != pre.value() {
-                counts.push((cur.value(), 1));
-                ind += 1;
-            } else {
-                counts[ind].1 += 1;
+        }
+        // (copies, value) of each value present, most copies first, then
+        // highest value; kept on the stack as this runs for every hand
+        let counts = value_counts(cards);
+        let mut groups = [(0, Value::Two); 5];
+        let mut len = 0;
+        for value in Value::ALL.into_iter().rev() {
+            let n = counts[value as usize - 2];
+            if n > 0 && len < groups.len() {
+                groups[len] = (n, value);
+                len += 1;
             }
-            pre = *cur;
         }
-        counts.sort_by(|a, b| b.1.cmp(&a.1));
-        match counts.len() {
+        let groups = &mut groups[..len];
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        match len {
             5 => {
-                let val = array::from_fn(|i| counts[i].0);
                 if is_straight {
                     if is_flush && cards[1].value() == Value::King {
-                        return Rank::RoyalStraightFlush;
+                        return Ok(Rank::RoyalStraightFlush);
                     }
-                    let v = if cards[0].value() == Value::Ace {
-                        cards[1].value()
+                    // the wheel is five high, any other straight its top card
+                    let v = if cards[0].value() == Value::Ace && cards[1].value() == Value::Five {
+                        Value::Five
                     } else {
                         cards[0].value()
                     };
                     if is_flush {
-                        return Rank::StraightFlush(v);
+                        return Ok(Rank::StraightFlush(v));
                     }
-                    return Rank::Straight(v);
+                    return Ok(Rank::Straight(v));
                 }
                 if is_flush {
-                    return Rank::Flush(val);
+                    return Ok(Rank::Flush(array::from_fn(|i| groups[i].1)));
                 }
-                return Rank::HighCard(val);
+                Ok(Rank::HighCard(array::from_fn(|i| groups[i].1)))
             }
-            4 => return Rank::Pair(array::from_fn(|i| counts[i].0)),
+            4 => Ok(Rank::Pair(array::from_fn(|i| groups[i].1))),
             3 => {
-                let val = array::from_fn(|i| counts[i].0);
-                if counts[0].1 == 2 {
-                    return Rank::TwoPair(val);
+                if groups[0].0 == 2 {
+                    return Ok(Rank::TwoPair(array::from_fn(|i| groups[i].1)));
                 }
-                return Rank::Set(val);
+                Ok(Rank::Set(array::from_fn(|i| groups[i].1)))
             }
             2 => {
-                let val = array::from_fn(|i| counts[i].0);
-                if counts[0].1 == 3 {
-                    return Rank::FullHouse(val);
+                if groups[0].0 == 3 {
+                    return Ok(Rank::FullHouse(array::from_fn(|i| groups[i].1)));
                 }
-                return Rank::Bomb(val);
+                Ok(Rank::Bomb(array::from_fn(|i| groups[i].1)))
             }
-            _ => panic!("no such rank invalid"),
+            // four of a kind is the most repeated value five distinct cards allow
+            _ => Err(Error::BadHand),
+        }
+    }
+}
+
+/// Indices of the best hands, more than one on a split pot. In `strict`
+/// mode no card may be in two hands.
+pub fn winner(hands: &[HoldemHand], strict: bool) -> Result<Vec<usize>, Error> {
+    if strict {
+        let cards: Vec<Card> = hands.iter().flat_map(|h| h.cards).collect();
+        check_duplicates(&cards)?;
+    }
+    let best = match hands.iter().max() {
+        Some(best) => best,
+        None => return Ok(Vec::new()),
+    };
+    Ok((0..hands.len()).filter(|&i| hands[i] == *best).collect())
+}
+
+/// How many hole cards a `BestHand` uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoleUsage {
+    Both,
+    One,
+    /// Playing the board.
+    None,
+}
+
+/// A seven-card evaluation from `HoldemHand::best_hand`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestHand {
+    pub hand: HoldemHand,
+    /// Each hole card, if it is part of `hand`.
+    pub hole_used: [Option<Card>; 2],
+}
+
+impl BestHand {
+    pub fn usage(&self) -> HoleUsage {
+        match self.hole_used.iter().flatten().count() {
+            2 => HoleUsage::Both,
+            1 => HoleUsage::One,
+            _ => HoleUsage::None,
         }
     }
 }
 
+/// The outcome of `showdown`, indexed by player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowdownResult {
+    /// Each player's best five-card hand.
+    pub hands: Vec<HoldemHand>,
+    /// The players sharing the pot.
+    pub winners: Vec<usize>,
+}
+
+impl ShowdownResult {
+    pub fn rank(&self, player: usize) -> Rank {
+        self.hands[player].rank
+    }
+}
+
+/// Evaluates every player's hole cards against a shared board.
+/// Fails if any card is dealt twice.
+pub fn showdown(players: &[[Card; 2]], board: [Card; 5]) -> Result<ShowdownResult, Error> {
+    let dealt: Vec<Card> = players.iter().flatten().chain(&board).copied().collect();
+    check_duplicates(&dealt)?;
+    let hands = players
+        .iter()
+        .map(|&hole| HoldemHand::best_of_hole_and_board(hole, board))
+        .collect::<Result<Vec<_>, _>>()?;
+    let winners = winner(&hands, false)?;
+    Ok(ShowdownResult { hands, winners })
+}
+
+pub(crate) fn check_duplicates(cards: &[Card]) -> Result<(), Error> {
+    match find_duplicate(cards) {
+        Some(card) => Err(Error::DuplicateCard(card)),
+        None => Ok(()),
+    }
+}
+
+impl PartialEq for HoldemHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl Eq for HoldemHand {}
+
+impl Ord for HoldemHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
+
+impl PartialOrd for HoldemHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl TryFrom<&str> for HoldemHand {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let cards: Vec<Card> = value
-            .split_whitespace()
-            .map(|s| Card::try_from(s))
-            .collect::<Result<_, _>>()?;
-        if cards.len() != 5 {
-            return Err(Error::BadCard("invalid number of cards".to_string()));
+        let (cards, errors) = parse_cards(value);
+        if let Some((_, e)) = errors.into_iter().next() {
+            return Err(e);
         }
-        Ok(Self::new(array::from_fn(|i| cards[i])))
+        Self::try_from(cards.as_slice())
+    }
+}
+
+/// Exactly five distinct cards, in any order.
+impl TryFrom<&[Card]> for HoldemHand {
+    type Error = Error;
+
+    fn try_from(cards: &[Card]) -> Result<Self, Self::Error> {
+        let cards: [Card; 5] = cards
+            .try_into()
+            .map_err(|_| Error::BadCard("invalid number of cards".to_string()))?;
+        Self::new(cards)
+    }
+}
+
+impl TryFrom<Vec<Card>> for HoldemHand {
+    type Error = Error;
+
+    fn try_from(cards: Vec<Card>) -> Result<Self, Self::Error> {
+        Self::try_from(cards.as_slice())
     }
 }
 
+impl<'a> IntoIterator for &'a HoldemHand {
+    type Item = Card;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, Card>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Panics if `index` is 5 or more, like indexing the array.
+impl std::ops::Index<usize> for HoldemHand {
+    type Output = Card;
+
+    fn index(&self, index: usize) -> &Card {
+        &self.cards[index]
+    }
+}
+
+impl AsRef<[Card]> for HoldemHand {
+    fn as_ref(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+impl FromStr for HoldemHand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl HoldemHand {
+    pub fn format_with(&self, style: CardStyle) -> String {
+        self.cards
+            .iter()
+            .map(|c| c.format_with(style))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// `{:#}` renders every card with its suit glyph.
 impl Display for HoldemHand {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.cards
-                .iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
+        let style = if f.alternate() {
+            CardStyle::Symbol
+        } else {
+            CardStyle::Standard
+        };
+        f.pad(&self.format_with(style))
+    }
+}
+
+/// Plain English, e.g. `Full House, Kings full of Tens`.
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let s = match self {
+            Rank::HighCard(v) => format!("High Card {}", v[0].name()),
+            Rank::Pair(v) => format!("Pair of {}", v[0].plural_name()),
+            Rank::TwoPair(v) => format!(
+                "Two Pair, {} and {}",
+                v[0].plural_name(),
+                v[1].plural_name()
+            ),
+            Rank::Set(v) => format!("Three of a Kind, {}", v[0].plural_name()),
+            Rank::Straight(v) => format!("Straight, {} high", v.name()),
+            Rank::Flush(v) => format!("Flush, {} high", v[0].name()),
+            Rank::FullHouse(v) => format!(
+                "Full House, {} full of {}",
+                v[0].plural_name(),
+                v[1].plural_name()
+            ),
+            Rank::Bomb(v) => format!("Four of a Kind, {}", v[0].plural_name()),
+            Rank::StraightFlush(v) => format!("Straight Flush, {} high", v.name()),
+            Rank::RoyalStraightFlush => "Royal Flush".to_string(),
+        };
+        f.pad(&s)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::{consts, sample_deals};
+
+    // the grouping evaluator `evaluate` replaced, kept to check it against
+    fn reference_evaluate(cards: &[Card; 5]) -> Result<Rank, Error> {
+        let is_flush = suit_counts(cards).contains(&5);
+        let mut is_straight = true;
+        for pair in cards.windows(2) {
+            let (pre, cur) = (pair[0], pair[1]);
+            is_straight &= cur.value() + 1 == pre.value()
+                || (pre.value() == Value::Ace && cur.value() == Value::Five);
+        }
+        let counts = cards.group_by_value();
+        match counts.len() {
+            5 => {
+                if is_straight {
+                    if is_flush && cards[1].value() == Value::King {
+                        return Ok(Rank::RoyalStraightFlush);
+                    }
+                    let v = if cards[0].value() == Value::Ace && cards[1].value() == Value::Five {
+                        Value::Five
+                    } else {
+                        cards[0].value()
+                    };
+                    if is_flush {
+                        return Ok(Rank::StraightFlush(v));
+                    }
+                    return Ok(Rank::Straight(v));
+                }
+                if is_flush {
+                    return Ok(Rank::Flush(array::from_fn(|i| counts[i].0)));
+                }
+                Ok(Rank::HighCard(array::from_fn(|i| counts[i].0)))
+            }
+            4 => Ok(Rank::Pair(array::from_fn(|i| counts[i].0))),
+            3 if counts[0].1.len() == 2 => Ok(Rank::TwoPair(array::from_fn(|i| counts[i].0))),
+            3 => Ok(Rank::Set(array::from_fn(|i| counts[i].0))),
+            2 if counts[0].1.len() == 3 => Ok(Rank::FullHouse(array::from_fn(|i| counts[i].0))),
+            2 => Ok(Rank::Bomb(array::from_fn(|i| counts[i].0))),
+            _ => Err(Error::BadHand),
+        }
+    }
+
+    // cargo test --release bench_evaluate -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_evaluate() {
+        use std::{hint::black_box, time::Instant};
+        let hands: Vec<[Card; 5]> = Rank::enumerate_classes().map(|(_, c)| c).collect();
+        let time = |evaluate: fn(&[Card; 5]) -> Result<Rank, Error>| {
+            let start = Instant::now();
+            for _ in 0..100 {
+                for cards in &hands {
+                    black_box(evaluate(black_box(cards))).unwrap();
+                }
+            }
+            start.elapsed()
+        };
+        let reference = time(reference_evaluate);
+        let current = time(HoldemHand::evaluate);
+        println!("reference: {:?}, evaluate: {:?}", reference, current);
+    }
+
+    #[test]
+    fn test_evaluate_matches_reference() {
+        let mut hands: Vec<[Card; 5]> = Rank::enumerate_classes().map(|(_, c)| c).collect();
+        hands.extend(sample_deals(0x2545_f491_4f6c_dd1d, 5000));
+        for mut cards in hands {
+            cards.sort_desc();
+            assert_eq!(
+                HoldemHand::evaluate(&cards),
+                reference_evaluate(&cards),
+                "{:?}",
+                cards
+            );
+        }
+    }
 
     #[test]
     fn test_tryfrom() {
@@ -148,6 +851,10 @@ mod tests {
                 Card::new(Club, Value::Two)
             ]
         );
+        // equal values keep their input order rather than sorting by suit
+        let hand = HoldemHand::try_from("9s Kd 9h 2c Kc").unwrap();
+        assert_eq!(hand.to_string(), "Kd Kc 9s 9h 2c");
+
         let hand = HoldemHand::try_from("2c 3c 4c 5c 6c 7c");
         assert_eq!(
             hand,
@@ -158,6 +865,67 @@ mod tests {
         assert_eq!(hand, Err(Error::BadSuit("k".to_string())));
         let hand = HoldemHand::try_from("1s 3c 4c 5c 6c");
         assert_eq!(hand, Err(Error::BadValue("1".to_string())));
+
+        let hand = HoldemHand::try_from("As Ts 9h 10d Kc").unwrap();
+        assert_eq!(hand.to_string(), "As Kc 10s 10d 9h");
+
+        let hand = HoldemHand::try_from("AsKdQs10h9c").unwrap();
+        assert_eq!(hand.to_string(), "As Kd Qs 10h 9c");
+
+        let hand = HoldemHand::try_from("A♠ K♦ 10♥ 9♣ 2s").unwrap();
+        assert_eq!(format!("{:#}", hand), "A♠ K♦ 10♥ 9♣ 2♠");
+        assert_eq!(hand.format_with(CardStyle::Compact), "As Kd Th 9c 2s");
+        assert_eq!(hand.format_with(CardStyle::Uppercase), "AS KD 10H 9C 2S");
+        assert_eq!(hand.format_with(CardStyle::Symbol), "A♠ K♦ 10♥ 9♣ 2♠");
+    }
+
+    #[test]
+    fn test_hand_macro() {
+        let hand = hand!("As Ks Qs Js 10s");
+        assert_eq!(hand.rank, Rank::RoyalStraightFlush);
+        let sorted = HoldemHand::try_from("10s Js Qs Ks As").unwrap();
+        assert_eq!(hand.cards(), sorted.cards());
+    }
+
+    #[test]
+    #[should_panic(expected = "Bad suit: x")]
+    fn test_hand_macro_invalid() {
+        hand!("As Ks Qs Js 10x");
+    }
+
+    #[test]
+    fn test_display_padding() {
+        let hand = HoldemHand::try_from("As Ks Qs Js 10s").unwrap();
+        assert_eq!(format!("{:>16}", hand), " As Ks Qs Js 10s");
+        assert_eq!(format!("{:.<17}", hand), "As Ks Qs Js 10s..");
+        assert_eq!(format!("{:#}", hand), "A♠ K♠ Q♠ J♠ 10♠");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let hand: HoldemHand = "2c 3c 4c 5c 6c".parse().unwrap();
+        let sorted = HoldemHand::try_from("6c 5c 4c 3c 2c").unwrap();
+        assert_eq!(hand.cards(), sorted.cards());
+        assert_eq!(
+            "2c 3c 4c 5c".parse::<HoldemHand>(),
+            Err(Error::BadCard("invalid number of cards".to_string()))
+        );
+        assert_eq!(
+            "2c 3c 4c 5c 6x".parse::<HoldemHand>(),
+            Err(Error::BadSuit("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_broadway_straight() {
+        use Value::*;
+        let hand = HoldemHand::try_from("As Kd Qs Js 10s").unwrap();
+        assert_eq!(hand.rank, Rank::Straight(Ace));
+        let hand = HoldemHand::try_from("Kd Qs Js 10s 9s").unwrap();
+        assert_eq!(hand.rank, Rank::Straight(King));
+        // the ace plays low in the wheel only
+        let hand = HoldemHand::try_from("As 2d 3s 4s 5s").unwrap();
+        assert_eq!(hand.rank, Rank::Straight(Five));
     }
 
     #[test]
@@ -173,7 +941,7 @@ mod tests {
         let hand = HoldemHand::try_from("2s 9c 9s 9d 9h").unwrap();
         assert_eq!(hand.rank, Rank::Bomb([Value::Nine, Two]));
 
-        let hand = HoldemHand::try_from("2c 2c 3c 3s 2h").unwrap();
+        let hand = HoldemHand::try_from("2c 2d 3c 3s 2h").unwrap();
         assert_eq!(hand.rank, Rank::FullHouse([Value::Two, Three]));
 
         let hand = HoldemHand::try_from("2c 3c qc ac 9c").unwrap();
@@ -199,7 +967,7 @@ mod tests {
         let second = HoldemHand::try_from("4c 3h 5d As 2s").unwrap();
         println!("{:?}", first.rank);
         println!("{:?}", second.rank);
-        assert_eq!(first.rank > second.rank, true);
+        assert!(first.rank > second.rank);
     }
 
     #[test]
@@ -208,19 +976,535 @@ mod tests {
         use Rank::*;
         use Value::*;
         assert_eq!(HighCard([Ace, King, Ten, Three, Two]), HighCard([Ace, King, Ten, Three, Two]));
-        assert_eq!(HighCard([Ace, King, Jack, Three, Two]) > HighCard([Ace, King, Ten, Three, Two]), true);
-        assert_eq!(HighCard([Ace, King, Ten, Three, Two]) < TwoPair([Two, Ace, King]), true);
-        assert_eq!(TwoPair([Two, Ace, King]) < TwoPair([Three, Ace, King]), true);
+        assert!(HighCard([Ace, King, Jack, Three, Two]) > HighCard([Ace, King, Ten, Three, Two]));
+        assert!(HighCard([Ace, King, Ten, Three, Two]) < TwoPair([Two, Ace, King]));
+        assert!(TwoPair([Two, Ace, King]) < TwoPair([Three, Ace, King]));
         assert_eq!(RoyalStraightFlush, RoyalStraightFlush);
-        assert_eq!(RoyalStraightFlush < RoyalStraightFlush, false);
-        assert_eq!(Bomb([Ace, Two]) > Bomb([King, Queen]), true);
-        assert_eq!(Bomb([Ace, Three]) > Bomb([Ace, Two]), true);
-        assert_eq!(Bomb([King, Queen]) > RoyalStraightFlush, false);
-        assert_eq!(Bomb([King, Queen]) > FullHouse([Ace, Two]), true);
-        assert_eq!(Bomb([King, Queen]) < StraightFlush(Ace), true);
-        assert_eq!(Pair([Ace, King, Queen, Jack]) > Pair([Ace, Queen, Jack, Two]), true);
-        assert_eq!(Pair([Ace, Queen, Jack, Three]) > Pair([Ace, Queen, Jack, Two]), true);
+        assert!(RoyalStraightFlush >= RoyalStraightFlush);
+        assert!(Bomb([Ace, Two]) > Bomb([King, Queen]));
+        assert!(Bomb([Ace, Three]) > Bomb([Ace, Two]));
+        assert!(Bomb([King, Queen]) <= RoyalStraightFlush);
+        assert!(Bomb([King, Queen]) > FullHouse([Ace, Two]));
+        assert!(Bomb([King, Queen]) < StraightFlush(Ace));
+        assert!(Pair([Ace, King, Queen, Jack]) > Pair([Ace, Queen, Jack, Two]));
+        assert!(Pair([Ace, Queen, Jack, Three]) > Pair([Ace, Queen, Jack, Two]));
         assert_eq!(Pair([Ace, Queen, Jack, Three]), Pair([Ace, Queen, Jack, Three]));
-        assert_eq!(Straight(Five) < Straight(Six), true);
+        assert!(Straight(Five) < Straight(Six));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        assert_eq!(
+            HoldemHand::try_from("2c 2c 3c 3s 2h"),
+            Err(Error::DuplicateCard(consts::TWO_OF_CLUBS))
+        );
+        assert_eq!(
+            "As Ks Qs Js As".parse::<HoldemHand>(),
+            Err(Error::DuplicateCard(consts::ACE_OF_SPADES))
+        );
+        let cards = Card::parse_many("9h 9d 9s Kh 9h").unwrap();
+        assert_eq!(
+            HoldemHand::best_from(&cards),
+            Err(Error::DuplicateCard(consts::NINE_OF_HEARTS))
+        );
+        assert_eq!(
+            Error::DuplicateCard(consts::NINE_OF_HEARTS).to_string(),
+            "Duplicate card: 9h"
+        );
+    }
+
+    #[test]
+    fn test_rank_category() {
+        use Value::*;
+        let bomb = Rank::Bomb([Two, Three]);
+        let full_house = Rank::FullHouse([Ace, King]);
+        assert!(bomb.category() > full_house.category());
+        assert_eq!(full_house.category(), RankCategory::FullHouse);
+        assert_eq!(
+            Rank::Set([Nine, Ace, King]).category().name(),
+            "Three of a Kind"
+        );
+
+        assert!(full_house.is_at_least(RankCategory::TwoPair));
+        assert!(full_house.is_at_least(RankCategory::FullHouse));
+        assert!(!full_house.is_at_least(RankCategory::Bomb));
+        assert!(Rank::RoyalStraightFlush.is_at_least(RankCategory::StraightFlush));
+
+        let ranks = [
+            Rank::HighCard([Ace, King, Queen, Jack, Nine]),
+            Rank::Pair([Two, Five, Four, Three]),
+            Rank::TwoPair([Three, Two, Four]),
+            Rank::Set([Two, Four, Three]),
+            Rank::Straight(Five),
+            Rank::Flush([Seven, Five, Four, Three, Two]),
+            full_house,
+            bomb,
+            Rank::StraightFlush(Five),
+            Rank::RoyalStraightFlush,
+        ];
+        for pair in ranks.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(pair[0].category() < pair[1].category());
+        }
+        let categories: Vec<_> = ranks.iter().map(Rank::category).collect();
+        assert_eq!(categories, RankCategory::ALL);
+    }
+
+    #[test]
+    fn test_rank_category_str() {
+        for category in RankCategory::ALL {
+            assert_eq!(category.as_str().parse(), Ok(category));
+        }
+        assert_eq!(RankCategory::Set.as_str(), "three_of_a_kind");
+        assert_eq!(RankCategory::Bomb.as_str(), "four_of_a_kind");
+        assert_eq!(RankCategory::RoyalStraightFlush.as_str(), "royal_flush");
+        assert_eq!("boat".parse::<RankCategory>(), Err(Error::BadRank));
+        assert_eq!("Pair".parse::<RankCategory>(), Err(Error::BadRank));
+    }
+
+    #[test]
+    fn test_score() {
+        use crate::poker::Rank as _;
+        use Value::*;
+        // strides through the deck give quads, straight flushes and the rest
+        let hands: Vec<HoldemHand> = (0..52)
+            .flat_map(|start| (1..=13).map(move |step| (start, step)))
+            .map(|(start, step)| array::from_fn(|i| Card::ALL[(start + i * step) % 52]))
+            .filter_map(|cards| HoldemHand::new(cards).ok())
+            .chain(
+                [
+                    "Ah Ad 3c 3s 9h",
+                    "7h 7d 7c Ks 2h",
+                    "Kh 9h 7h 4h 2h",
+                    "Kh Kd Kc 10s 10h",
+                    "9h 8d 7c 6s 5h",
+                ]
+                .map(|s| HoldemHand::try_from(s).unwrap()),
+            )
+            .collect();
+        let categories: std::collections::HashSet<_> =
+            hands.iter().map(|h| h.rank.category()).collect();
+        assert_eq!(categories.len(), 10);
+        for a in &hands {
+            assert_eq!(Rank::from_score(a.rank.to_score()), Ok(a.rank));
+            assert_eq!(a.rank.rank(), a.rank.to_score());
+            for b in &hands {
+                assert_eq!(
+                    a.rank.cmp(&b.rank),
+                    a.rank.to_score().cmp(&b.rank.to_score())
+                );
+            }
+        }
+
+        assert_eq!(Rank::RoyalStraightFlush.to_score(), 9 << 20);
+        assert_eq!(Rank::Straight(Five).to_score(), 4 << 20 | 5 << 16);
+        assert_eq!(Rank::from_score(10 << 20), Err(Error::BadRank));
+        assert_eq!(Rank::from_score(4 << 20 | 1 << 16), Err(Error::BadRank));
+        assert_eq!(Rank::from_score(4 << 20 | 5 << 16 | 3), Err(Error::BadRank));
+    }
+
+    #[test]
+    fn test_from_score_impossible() {
+        use Value::*;
+        let impossible = [
+            Rank::Straight(Two),
+            Rank::StraightFlush(Ace),
+            Rank::HighCard([Ace; 5]),
+            Rank::HighCard([Six, Five, Four, Three, Two]),
+            Rank::Pair([Ace, Ace, King, Queen]),
+            Rank::FullHouse([King, King]),
+        ];
+        for rank in impossible {
+            assert_eq!(Rank::from_score(rank.to_score()), Err(Error::BadRank));
+        }
+    }
+
+    #[test]
+    fn test_representative() {
+        use Value::*;
+        for rank in [
+            Rank::HighCard([King, Jack, Nine, Four, Two]),
+            Rank::Pair([Two, Ace, King, Queen]),
+            Rank::TwoPair([Ace, King, Two]),
+            Rank::Set([Six, Ace, Two]),
+            Rank::Straight(Five),
+            Rank::Straight(Ace),
+            Rank::Flush([Ace, Jack, Nine, Four, Two]),
+            Rank::FullHouse([Two, Ace]),
+            Rank::Bomb([Ace, King]),
+            Rank::StraightFlush(Five),
+            Rank::RoyalStraightFlush,
+        ] {
+            let cards = rank.representative().unwrap();
+            assert_eq!(HoldemHand::new(cards).unwrap().rank, rank);
+        }
+        assert_eq!(Rank::Pair([King, King, Nine, Four]).representative(), None);
+        assert_eq!(
+            Rank::HighCard([Six, Five, Four, Three, Two]).representative(),
+            None
+        );
+        assert_eq!(Rank::Straight(Four).representative(), None);
+        assert_eq!(Rank::StraightFlush(Ace).representative(), None);
+        assert_eq!(Rank::TwoPair([King, Ace, Two]).representative(), None);
+    }
+
+    #[test]
+    fn test_class_index() {
+        use Value::*;
+        let royal = Rank::RoyalStraightFlush;
+        let worst = Rank::HighCard([Seven, Five, Four, Three, Two]);
+        assert_eq!(royal.class_index(), 0);
+        assert_eq!(Rank::StraightFlush(King).class_index(), 1);
+        assert_eq!(worst.class_index(), Rank::CLASS_COUNT - 1);
+        assert_eq!(royal.percentile(), 100.0);
+        assert_eq!(worst.percentile(), 0.0);
+
+        // 10 straight flushes and 156 quads come before the best full house
+        assert_eq!(Rank::FullHouse([Ace, King]).class_index(), 166);
+        let hands = ["Ah Ad Kc Ks 2h", "Ah Ad Kc Ks 3h", "9h 9d 9c 2s 3h"]
+            .map(|s| s.parse::<HoldemHand>().unwrap().rank);
+        assert!(hands[0].class_index() > hands[1].class_index());
+        assert!(hands[1].class_index() > hands[2].class_index());
+        assert!((50.0..100.0).contains(&hands[2].percentile()));
+    }
+
+    #[test]
+    fn test_enumerate_classes() {
+        let classes: Vec<_> = Rank::enumerate_classes().collect();
+        assert_eq!(classes.len(), Rank::CLASS_COUNT as usize);
+        assert_eq!(Rank::from_class_index(Rank::CLASS_COUNT), None);
+        assert!(classes.windows(2).all(|w| w[0].0 > w[1].0));
+        for (i, (rank, cards)) in classes.iter().enumerate() {
+            assert_eq!(HoldemHand::new(*cards).unwrap().rank, *rank);
+            assert_eq!(rank.class_index() as usize, i);
+            assert_eq!(Rank::from_class_index(i as u16), Some(*rank));
+        }
+
+        let count = |category| {
+            let classes = classes
+                .iter()
+                .filter(|(rank, _)| rank.category() == category);
+            classes.count()
+        };
+        let expected = [1277, 2860, 858, 858, 10, 1277, 156, 156, 9, 1];
+        for (category, n) in RankCategory::ALL.into_iter().zip(expected) {
+            assert_eq!(count(category), n, "{}", category.name());
+        }
+    }
+
+    #[test]
+    fn test_kickers() {
+        use Value::*;
+        let split = |s| {
+            let hand = HoldemHand::try_from(s).unwrap();
+            (
+                hand.rank().primary_values().to_vec(),
+                hand.kickers().to_vec(),
+            )
+        };
+        assert_eq!(
+            split("Ah Qd 9c 4s 2h"),
+            (vec![Ace], vec![Queen, Nine, Four, Two])
+        );
+        assert_eq!(
+            split("Qh Qd 9c 4s Ah"),
+            (vec![Queen], vec![Ace, Nine, Four])
+        );
+        assert_eq!(split("Ah Ad 3c 3s 9h"), (vec![Ace, Three], vec![Nine]));
+        assert_eq!(split("7h 7d 7c Ks 2h"), (vec![Seven], vec![King, Two]));
+        assert_eq!(split("Ah 2d 3c 4s 5h"), (vec![Five], vec![]));
+        assert_eq!(
+            split("Kh 9h 7h 4h 2h"),
+            (vec![King, Nine, Seven, Four, Two], vec![])
+        );
+        assert_eq!(split("Kh Kd Kc 10s 10h"), (vec![King, Ten], vec![]));
+        assert_eq!(split("6h 6d 6c 6s Ah"), (vec![Six], vec![Ace]));
+        assert_eq!(split("Jh 10h 9h 8h 7h"), (vec![Jack], vec![]));
+        assert_eq!(split("Ah Kh Qh Jh 10h"), (vec![Ace], vec![]));
+    }
+
+    #[test]
+    fn test_winner() {
+        let hand = |s| HoldemHand::try_from(s).unwrap();
+        let hands = [
+            hand("9h 8d 7c 6s 5h"),
+            hand("9c 8s 7d 6h 5c"),
+            hand("Ah Ad Kc Ks Qh"),
+        ];
+        assert_eq!(winner(&hands, true), Ok(vec![0, 1]));
+        assert_eq!(hands[0].compare(&hands[1]), Ordering::Equal);
+        assert_eq!(hands[2].compare(&hands[0]), Ordering::Less);
+        assert_eq!(hands[0].compare_strict(&hands[2]), Ok(Ordering::Greater));
+        assert_eq!(winner(&hands[2..], true), Ok(vec![0]));
+        assert_eq!(winner(&[], true), Ok(vec![]));
+
+        let shared = [hand("9h 8d 7c 6s 5h"), hand("9h Kd Kc 2s 3h")];
+        assert_eq!(
+            winner(&shared, true),
+            Err(Error::DuplicateCard(consts::NINE_OF_HEARTS))
+        );
+        assert_eq!(winner(&shared, false), Ok(vec![0]));
+        assert_eq!(
+            shared[0].compare_strict(&shared[1]),
+            Err(Error::DuplicateCard(consts::NINE_OF_HEARTS))
+        );
+    }
+
+    #[test]
+    fn test_showdown() {
+        use Value::*;
+        let cards = |s: &str| Card::parse_many(s).unwrap();
+        let hole = |s: &str| -> [Card; 2] { array::from_fn(|i| cards(s)[i]) };
+        let board = |s: &str| -> [Card; 5] { array::from_fn(|i| cards(s)[i]) };
+
+        let result = showdown(
+            &[hole("9h 2c"), hole("9d 3c"), hole("Ah Ad")],
+            board("5s 6d 7c 8h Kd"),
+        )
+        .unwrap();
+        assert_eq!(result.winners, [0, 1]);
+        assert_eq!(result.rank(0), Rank::Straight(Nine));
+        assert_eq!(result.rank(2), Rank::Pair([Ace, King, Eight, Seven]));
+
+        let result = showdown(&[hole("Ah 9c"), hole("Ad Kc")], board("As 7d 5c 3h 2s")).unwrap();
+        assert_eq!(result.winners, [1]);
+        assert_eq!(result.hands[1].kickers(), [King, Seven, Five]);
+
+        assert_eq!(
+            showdown(&[hole("Ah 9c"), hole("As Kc")], board("As 7d 5c 3h Qs")),
+            Err(Error::DuplicateCard(consts::ACE_OF_SPADES))
+        );
+    }
+
+    #[test]
+    fn test_rank_display() {
+        let rank = |s| HoldemHand::try_from(s).unwrap().rank.to_string();
+        assert_eq!(rank("Ah Qd 9c 4s 2h"), "High Card Ace");
+        assert_eq!(rank("Qh Qd 9c 4s Ah"), "Pair of Queens");
+        assert_eq!(rank("Ah Ad 3c 3s 9h"), "Two Pair, Aces and Threes");
+        assert_eq!(rank("7h 7d 7c Ks 2h"), "Three of a Kind, Sevens");
+        assert_eq!(rank("9h 8d 7c 6s 5h"), "Straight, Nine high");
+        assert_eq!(rank("Ah 2d 3c 4s 5h"), "Straight, Five high");
+        assert_eq!(rank("Kh 9h 7h 4h 2h"), "Flush, King high");
+        assert_eq!(rank("Kh Kd Kc 10s 10h"), "Full House, Kings full of Tens");
+        assert_eq!(rank("6h 6d 6c 6s Ah"), "Four of a Kind, Sixes");
+        assert_eq!(rank("Jh 10h 9h 8h 7h"), "Straight Flush, Jack high");
+        assert_eq!(rank("Ah 2h 3h 4h 5h"), "Straight Flush, Five high");
+        assert_eq!(rank("Ah Kh Qh Jh 10h"), "Royal Flush");
+        assert_eq!(format!("{:>12}", Rank::RoyalStraightFlush), " Royal Flush");
+    }
+
+    #[test]
+    fn test_new_never_panics() {
+        use consts::*;
+        assert_eq!(
+            HoldemHand::new([ACE_OF_SPADES; 5]),
+            Err(Error::DuplicateCard(ACE_OF_SPADES))
+        );
+        for card in Card::ALL {
+            for other in Card::ALL {
+                let result = HoldemHand::new([card, other, card, other, card]);
+                assert_eq!(result, Err(Error::DuplicateCard(card)));
+            }
+        }
+        let quads = [TWO_OF_HEARTS, TWO_OF_DIAMONDS, TWO_OF_CLUBS, TWO_OF_SPADES];
+        for card in Card::ALL {
+            let hand = HoldemHand::new([quads[0], quads[1], quads[2], quads[3], card]);
+            if quads.contains(&card) {
+                assert_eq!(hand, Err(Error::DuplicateCard(card)));
+            } else {
+                assert!(matches!(hand.map(|h| h.rank), Ok(Rank::Bomb(_))));
+            }
+        }
+        assert!(HoldemHand::try_from("2c 2c 2c 2c 2c").is_err());
+        assert!(HoldemHand::best_from(&[ACE_OF_SPADES; 7]).is_err());
+    }
+
+    #[test]
+    fn test_best_of_seven() {
+        use Value::*;
+        let cards = |s: &str| Card::parse_many(s).unwrap();
+        let seven = |hole: &str, board: &str| {
+            let (hole, board) = (cards(hole), cards(board));
+            HoldemHand::best_of_hole_and_board(
+                array::from_fn(|i| hole[i]),
+                array::from_fn(|i| board[i]),
+            )
+            .unwrap()
+        };
+
+        // playing the board
+        let hand = seven("2c 3d", "As Ks Qs Js 10s");
+        assert_eq!(hand.rank, Rank::RoyalStraightFlush);
+        assert!(!hand.cards.iter().any(|c| c.to_string() == "2c"));
+
+        let flush = seven("Ah 3h", "Kh Kd 9h 9c 2h");
+        assert_eq!(flush.rank, Rank::Flush([Ace, King, Nine, Three, Two]));
+        let full_house = seven("Ks 4c", "Kh Kd 9h 9c 2h");
+        assert_eq!(full_house.rank, Rank::FullHouse([King, Nine]));
+        assert!(full_house > flush);
+
+        let wheel = seven("As 2d", "3c 4h 5s 9d Kc");
+        assert_eq!(wheel.rank, Rank::Straight(Five));
+        assert_eq!(wheel.to_string(), "As 5s 4h 3c 2d");
+
+        let seven: Vec<Card> = cards("As Ks 3c 4h 5s 9d As");
+        assert_eq!(
+            HoldemHand::best_of_seven(array::from_fn(|i| seven[i])),
+            Err(Error::DuplicateCard(consts::ACE_OF_SPADES))
+        );
+    }
+
+    #[test]
+    fn test_iter_index() {
+        let hand = HoldemHand::try_from("2c Kd 9h As 9s").unwrap();
+        let cards: Vec<Card> = hand.iter().collect();
+        assert_eq!(cards, hand.cards());
+        assert_eq!((&hand).into_iter().collect::<Vec<_>>(), cards);
+        let mut n = 0;
+        for card in &hand {
+            assert_eq!(card, hand[n]);
+            n += 1;
+        }
+        assert_eq!(n, 5);
+        assert_eq!(hand[0], consts::ACE_OF_SPADES);
+        assert_eq!(hand[4], consts::TWO_OF_CLUBS);
+        assert_eq!(hand.as_ref(), &cards[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let hand = HoldemHand::try_from("2c Kd 9h As 9s").unwrap();
+        let _ = hand[5];
+    }
+
+    #[test]
+    fn test_try_from_cards() {
+        let mut deck = Card::ALL.to_vec();
+        let mut deal = |n: usize| deck.split_off(deck.len() - n);
+
+        let hand = HoldemHand::try_from(deal(5)).unwrap();
+        assert_eq!(hand.to_string(), "Ah Ad Ac As Ks");
+        let cards = deal(5);
+        assert_eq!(
+            HoldemHand::try_from(cards.as_slice()),
+            HoldemHand::try_from(cards)
+        );
+
+        let invalid = Err(Error::BadCard("invalid number of cards".to_string()));
+        assert_eq!(HoldemHand::try_from(deal(4)), invalid);
+        assert_eq!(HoldemHand::try_from(deal(6)), invalid);
+        assert_eq!(HoldemHand::try_from(&[][..]), invalid);
+        assert_eq!(
+            HoldemHand::try_from(vec![consts::TWO_OF_CLUBS; 5]),
+            Err(Error::DuplicateCard(consts::TWO_OF_CLUBS))
+        );
+    }
+
+    #[test]
+    fn test_best_hand() {
+        use consts::*;
+        let cards = |s: &str| Card::parse_many(s).unwrap();
+        let best = |hole: &str, board: &str| {
+            let (hole, board) = (cards(hole), cards(board));
+            HoldemHand::best_hand([hole[0], hole[1]], array::from_fn(|i| board[i])).unwrap()
+        };
+
+        let board = best("2c 3d", "As Ks Qs Js 10s");
+        assert_eq!(board.usage(), HoleUsage::None);
+        assert_eq!(board.hole_used, [None, None]);
+
+        let flush = best("Ah 2c", "Kh 9h 7h 4h Qs");
+        assert!(matches!(flush.hand.rank, Rank::Flush(_)));
+        assert_eq!(flush.usage(), HoleUsage::One);
+        assert_eq!(flush.hole_used, [Some(ACE_OF_HEARTS), None]);
+
+        // the board straight ties with one using the 9h, which is preferred
+        let straight = best("9h 2c", "5s 6d 7c 8h 9s");
+        assert_eq!(straight.hand.rank, Rank::Straight(Value::Nine));
+        assert_eq!(straight.usage(), HoleUsage::One);
+        assert_eq!(straight.hole_used, [Some(NINE_OF_HEARTS), None]);
+
+        let both = best("Ah Ad", "As 7d 5c 3h Kc");
+        assert_eq!(both.usage(), HoleUsage::Both);
+
+        let duplicate = cards("As 7d 5c 3h Kc");
+        assert_eq!(
+            HoldemHand::best_hand(
+                [ACE_OF_SPADES, ACE_OF_HEARTS],
+                array::from_fn(|i| duplicate[i])
+            ),
+            Err(Error::DuplicateCard(ACE_OF_SPADES))
+        );
+    }
+
+    #[test]
+    fn test_best_from() {
+        use Value::*;
+        let cards = Card::parse_many("9h 9d 9s Kh Qh Jh 10h").unwrap();
+        assert_eq!(
+            HoldemHand::best_from(&cards[..5]),
+            HoldemHand::new(array::from_fn(|i| cards[i]))
+        );
+
+        let turn = Card::parse_many("9h 9d 9s Kd Qh Kh").unwrap();
+        let best = HoldemHand::best_from(&turn).unwrap();
+        assert_eq!(best.rank, Rank::FullHouse([Nine, King]));
+        let ranks: Vec<Rank> = (0..6)
+            .map(|skip| {
+                let five: Vec<Card> = (0..6).filter(|&i| i != skip).map(|i| turn[i]).collect();
+                HoldemHand::best_from(&five).unwrap().rank
+            })
+            .collect();
+        assert_eq!(
+            ranks,
+            [
+                Rank::TwoPair([King, Nine, Queen]),
+                Rank::TwoPair([King, Nine, Queen]),
+                Rank::TwoPair([King, Nine, Queen]),
+                Rank::Set([Nine, King, Queen]),
+                Rank::FullHouse([Nine, King]),
+                Rank::Set([Nine, King, Queen]),
+            ]
+        );
+
+        assert_eq!(HoldemHand::best_from(&cards[..4]), Err(Error::BadHand));
+        let eight = Card::parse_many("9h 9d 9s Kh Qh Jh 10h 2c").unwrap();
+        assert_eq!(HoldemHand::best_from(&eight), Err(Error::BadHand));
+        assert_eq!(HoldemHand::best_from(&[]), Err(Error::BadHand));
+    }
+
+    #[test]
+    fn test_hand_order() {
+        let hand = |s| HoldemHand::try_from(s).unwrap();
+        assert!(hand("4c 3h 5d 7s 6s") > hand("2c 3h ad 3s 2s"));
+        assert_eq!(hand("2c 5c 9c Jc Kc"), hand("2d 5d 9d Jd Kd"));
+        assert_eq!(
+            hand("As Ks Qs Js 10s").cmp(&hand("Ah Kh Qh Jh 10h")),
+            std::cmp::Ordering::Equal
+        );
+        assert!(hand("As Kd Qs Js 10s") > hand("Ks Qd Js 10s 9s"));
+        assert!(hand("As 2d 3s 4s 5s") < hand("2d 3s 4s 5s 6h"));
+
+        let mut hands = [
+            hand("As Ks Qs Js 10s"),
+            hand("2c 3h ad 3s 2s"),
+            hand("4c 3h 5d 7s 6s"),
+            hand("2c 3h 2d 2s as"),
+            hand("2c kh ad js 2s"),
+        ];
+        hands.sort();
+        let sorted: Vec<String> = hands.iter().map(|h| h.to_string()).collect();
+        assert_eq!(
+            sorted,
+            [
+                "Ad Kh Js 2c 2s",
+                "Ad 3h 3s 2c 2s",
+                "As 3h 2c 2d 2s",
+                "7s 6s 5d 4c 3h",
+                "As Ks Qs Js 10s",
+            ]
+        );
     }
 }
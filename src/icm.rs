@@ -0,0 +1,123 @@
+//! Tournament equity from chip stacks, by the Independent Chip Model.
+
+use std::collections::BTreeMap;
+
+/// Each player's share of `payouts`, first place first, by the
+/// Malmuth-Harville model: a player finishes first with the share of
+/// all chips they hold, then each next place goes the same way among the
+/// players left. Equities are in payout units.
+///
+/// Players with no chips are out and win nothing; `payouts` are for the
+/// places still to be decided, and places past the last player with chips
+/// pay no one. The chance of each set of players taking the top places
+/// is worked out once, whatever their order, so nine players paying nine
+/// places take 511 sets.
+///
+/// ```
+/// use poker::icm::icm;
+///
+/// let equities = icm(&[3000, 1000], &[100, 0]);
+/// assert_eq!(equities, [75.0, 25.0]);
+/// ```
+pub fn icm(stacks: &[u64], payouts: &[u64]) -> Vec<f64> {
+    let live: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i] > 0).collect();
+    let chips: f64 = stacks.iter().map(|&s| s as f64).sum();
+    let mut equities = vec![0.0; stacks.len()];
+
+    // the players taking the places so far, in index order, with the
+    // chance they do in any order and the chips the others hold
+    let mut finished: BTreeMap<Vec<usize>, (f64, f64)> = BTreeMap::new();
+    finished.insert(Vec::new(), (1.0, chips));
+    for &payout in payouts.iter().take(live.len()) {
+        let mut next = BTreeMap::new();
+        for (players, &(chance, left)) in &finished {
+            for &i in live.iter().filter(|i| !players.contains(i)) {
+                let stack = stacks[i] as f64;
+                let chance = chance * stack / left;
+                equities[i] += chance * payout as f64;
+
+                let mut players = players.clone();
+                let at = players.partition_point(|&p| p < i);
+                players.insert(at, i);
+                next.entry(players).or_insert((0.0, left - stack)).0 += chance;
+            }
+        }
+        finished = next;
+    }
+    equities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_heads_up() {
+        // with the loser paid, the difference splits by stacks
+        for (a, b) in [(1, 1), (3000, 1000), (1, 999), (12_345, 67_890)] {
+            let share = a as f64 / (a + b) as f64;
+            let equities = icm(&[a, b], &[100, 0]);
+            assert!(close(&equities, &[100.0 * share, 100.0 * (1.0 - share)]));
+            let equities = icm(&[a, b], &[70, 30]);
+            assert!(close(
+                &equities,
+                &[30.0 + 40.0 * share, 30.0 + 40.0 * (1.0 - share)]
+            ));
+        }
+    }
+
+    #[test]
+    fn test_three_players() {
+        // first 50/30/20, second from what's left, third the rest
+        let equities = icm(&[5000, 3000, 2000], &[50, 30, 20]);
+        let first = 50.0 * 0.5 + 30.0 * (0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0);
+        let third = 20.0 * (1.0 - 0.5 - (0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0));
+        assert!((equities[0] - (first + third)).abs() < 1e-9);
+        assert!(close(
+            &equities,
+            &[38.392857142857146, 32.75, 28.857142857142858]
+        ));
+        // the chip leader's equity is less than their chip share
+        assert!(equities[0] < 50.0 && equities[2] > 20.0);
+    }
+
+    #[test]
+    fn test_final_table() {
+        let stacks = [5000, 12_000, 800, 31_000, 9000, 9000, 15_500, 2200, 7000];
+        let payouts = [3000, 1900, 1300, 900, 700, 550, 450, 380, 320];
+        let equities = icm(&stacks, &payouts);
+        let pool: u64 = payouts.iter().sum();
+        assert!((equities.iter().sum::<f64>() - pool as f64).abs() < 1e-6);
+        // more chips, more equity; equal stacks, equal equity
+        assert!(equities[3] > equities[6] && equities[6] > equities[1]);
+        assert!((equities[4] - equities[5]).abs() < 1e-9);
+        assert!(equities.iter().all(|e| (320.0..=3000.0).contains(e)));
+
+        let equal = icm(&[100; 6], &[50, 30, 20]);
+        assert!(close(&equal, &[100.0 / 6.0; 6]));
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        // places past the players pay no one
+        let equities = icm(&[300, 100], &[50, 30, 20]);
+        assert!(close(&equities, &[30.0 + 20.0 * 0.75, 30.0 + 20.0 * 0.25]));
+        assert!((equities.iter().sum::<f64>() - 80.0).abs() < 1e-9);
+
+        // a busted player wins nothing and takes no place
+        let equities = icm(&[300, 0, 100], &[50, 30]);
+        assert!(close(&equities, &[45.0, 0.0, 35.0]));
+        assert_eq!(icm(&[0, 0], &[50, 30]), [0.0, 0.0]);
+        assert_eq!(icm(&[10], &[50, 30]), [50.0]);
+        assert!(icm(&[], &[50]).is_empty());
+        assert_eq!(icm(&[10, 20], &[]), [0.0, 0.0]);
+        assert_eq!(
+            icm(&[u64::MAX, u64::MAX], &[u64::MAX, 0]),
+            [u64::MAX as f64 / 2.0; 2]
+        );
+    }
+}
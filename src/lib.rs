@@ -1,10 +1,37 @@
+pub mod art;
+pub mod blockers;
+pub mod board;
+pub mod cache;
+pub mod canonical;
 pub mod card;
-pub mod poker;
-mod holdem;
+pub mod compare;
+pub mod draws;
+pub mod equity;
 mod error;
+pub mod ev;
+pub mod fast;
+pub mod format;
+mod holdem;
+pub mod icm;
+pub mod lookup;
+pub mod nuts;
+pub mod odds;
+#[allow(dead_code)]
+pub mod poker;
+pub mod preflop;
+#[cfg(feature = "rand")]
+mod rand_impls;
+pub mod range;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub use card::ParseCardExt;
+pub use error::Error;
+pub use holdem::{
+    showdown, winner, BestHand, HoldemHand, HoleUsage, Rank, RankCategory, ShowdownResult,
+};
 
-}
+#[cfg(test)]
+mod tests {}
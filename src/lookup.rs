@@ -0,0 +1,293 @@
+//! A jump table for seven-card evaluation.
+//!
+//! Each state of the table is the multiset of values seen so far; reading
+//! a card moves to the state with its value added, and the seventh card
+//! lands directly on the class index of the best five-card hand. Flushes
+//! are looked up separately by the values held in the flush suit. The
+//! table takes a few seconds to build, so build it once and `save` it.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{card::Card, error::Error, fast::eval5, holdem::Rank};
+
+const MAGIC: &[u8; 4] = b"PKLT";
+const VERSION: u32 = 1;
+const VALUES: usize = 13;
+// no state: a fifth copy of a value, or off the end after a bad file
+const INVALID: u32 = u32::MAX;
+
+/// Seven-card evaluator backed by a precomputed table.
+///
+/// ```no_run
+/// use poker::{card::Card, lookup::LookupTable};
+///
+/// let table = LookupTable::build();
+/// table.save("poker.table").unwrap();
+/// let table = LookupTable::load("poker.table").unwrap();
+/// let cards = Card::parse_many("As Ks Qs Js 10s 2c 2d").unwrap();
+/// assert_eq!(table.eval7(&cards.try_into().unwrap()), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupTable {
+    // VALUES entries per state: the next state, or a class index after
+    // the seventh card
+    next: Vec<u32>,
+    // best flush class by the value bits of the five or more suited cards
+    flushes: Vec<u16>,
+}
+
+impl LookupTable {
+    /// Generates the table.
+    pub fn build() -> LookupTable {
+        let mut states = vec![[0u8; VALUES]];
+        let mut ids = HashMap::from([([0u8; VALUES], 0)]);
+        let mut sevens = HashMap::new();
+        let mut next = Vec::new();
+        let mut i = 0;
+        while i < states.len() {
+            let counts = states[i];
+            let size: u8 = counts.iter().sum();
+            for value in 0..VALUES {
+                let mut added = counts;
+                added[value] += 1;
+                let entry = if counts[value] == 4 {
+                    INVALID
+                } else if size == 6 {
+                    *sevens
+                        .entry(added)
+                        .or_insert_with(|| u32::from(best_offsuit(&added)))
+                } else {
+                    *ids.entry(added).or_insert_with(|| {
+                        states.push(added);
+                        states.len() as u32 - 1
+                    })
+                };
+                next.push(entry);
+            }
+            i += 1;
+        }
+        let flushes = (0..1u16 << VALUES).map(best_flush).collect();
+        LookupTable { next, flushes }
+    }
+
+    /// The class index of the best five of seven distinct cards, as
+    /// `Rank::class_index` numbers them. Duplicate cards give a
+    /// meaningless result but never panic.
+    pub fn eval7(&self, cards: &[Card; 7]) -> u16 {
+        let mut state = 0;
+        let mut suits = [0u16; 4];
+        for card in cards {
+            let value = card.value() as usize - 2;
+            suits[card.suit().index() as usize] |= 1 << value;
+            state = match self.next.get(state as usize * VALUES + value) {
+                Some(&next) => next,
+                None => INVALID,
+            };
+        }
+        let best = u16::try_from(state).unwrap_or(u16::MAX);
+        match suits.iter().find(|bits| bits.count_ones() >= 5) {
+            Some(&bits) => best.min(self.flushes[bits as usize]),
+            None => best,
+        }
+    }
+
+    /// Writes the table to `path`, see `write_to`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a table saved by `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<LookupTable, Error> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes a header (magic, version, lengths, checksum) and then the
+    /// entries, little-endian.
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), Error> {
+        let payload = self.payload();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.next.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.flushes.len() as u64).to_le_bytes())?;
+        writer.write_all(&checksum(&payload).to_le_bytes())?;
+        writer.write_all(&payload)?;
+        Ok(())
+    }
+
+    /// Reads a table written by `write_to`, rejecting anything with the
+    /// wrong magic, version, lengths or checksum.
+    pub fn read_from(mut reader: impl Read) -> Result<LookupTable, Error> {
+        let bad = |msg: &str| Error::BadTable(msg.to_string());
+        let mut header = [0; 32];
+        reader
+            .read_exact(&mut header)
+            .map_err(|_| bad("truncated header"))?;
+        if &header[..4] != MAGIC {
+            return Err(bad("not a lookup table"));
+        }
+        let field = |at: usize, len: usize| {
+            let mut bytes = [0; 8];
+            bytes[..len].copy_from_slice(&header[at..at + len]);
+            u64::from_le_bytes(bytes)
+        };
+        let version = field(4, 4);
+        if version != u64::from(VERSION) {
+            return Err(Error::BadTable(format!("unsupported version {}", version)));
+        }
+        let (next_len, flushes_len) = (field(8, 8), field(16, 8));
+        if next_len % VALUES as u64 != 0 || flushes_len != 1 << VALUES {
+            return Err(bad("bad table lengths"));
+        }
+        let expected = next_len
+            .checked_mul(4)
+            .and_then(|n| n.checked_add(flushes_len * 2))
+            .ok_or_else(|| bad("bad table lengths"))?;
+        let mut payload = Vec::new();
+        reader.take(expected + 1).read_to_end(&mut payload)?;
+        if payload.len() as u64 != expected {
+            return Err(bad("wrong payload size"));
+        }
+        if checksum(&payload) != field(24, 8) {
+            return Err(bad("checksum mismatch"));
+        }
+
+        let (next, flushes) = payload.split_at(next_len as usize * 4);
+        let table = LookupTable {
+            next: next
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            flushes: flushes
+                .chunks_exact(2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                .collect(),
+        };
+        Ok(table)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let next = self.next.iter().flat_map(|n| n.to_le_bytes());
+        let flushes = self.flushes.iter().flat_map(|n| n.to_le_bytes());
+        next.chain(flushes).collect()
+    }
+}
+
+// FNV-1a
+pub(crate) fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// best five of seven cards with these value counts and no flush
+fn best_offsuit(counts: &[u8; VALUES]) -> u16 {
+    let values = (0..VALUES).flat_map(|v| std::iter::repeat_n(v, counts[v] as usize));
+    // copies of a value sit next to each other and so get distinct suits
+    let cards: Vec<Card> = values
+        .enumerate()
+        .map(|(i, v)| Card::ALL[v * 4 + i % 4])
+        .collect();
+    best_of(&cards)
+}
+
+// best flush with the values in `bits` all of one suit
+fn best_flush(bits: u16) -> u16 {
+    if bits.count_ones() < 5 {
+        return u16::MAX;
+    }
+    let cards: Vec<Card> = (0..VALUES)
+        .filter(|v| bits & 1 << v != 0)
+        .map(|v| Card::ALL[v * 4])
+        .collect();
+    best_of(&cards)
+}
+
+fn best_of(cards: &[Card]) -> u16 {
+    let n = cards.len();
+    let mut best = Rank::CLASS_COUNT;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        best = best.min(eval5(&[cards[a], cards[b], cards[c], cards[d], cards[e]]));
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::{card::sample_deals, HoldemHand};
+
+    fn table() -> &'static LookupTable {
+        static TABLE: OnceLock<LookupTable> = OnceLock::new();
+        TABLE.get_or_init(LookupTable::build)
+    }
+
+    fn written(table: &LookupTable) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_eval7() {
+        for cards in sample_deals::<7>(11, 3000) {
+            let best = HoldemHand::best_of_seven(cards).unwrap();
+            assert_eq!(table().eval7(&cards), best.rank().class_index(), "{}", best);
+        }
+        let cards = Card::parse_many("As Ks Qs Js 10s 2c 2d").unwrap();
+        assert_eq!(table().eval7(&cards.try_into().unwrap()), 0);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!("poker-{}.table", std::process::id()));
+        table().save(&path).unwrap();
+        let loaded = LookupTable::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(&loaded.unwrap(), table());
+        assert!(matches!(LookupTable::load(&path), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_bad_file() {
+        let bytes = written(table());
+        let read = |bytes: &[u8]| LookupTable::read_from(bytes).unwrap_err();
+        let bad = |msg: &str| Error::BadTable(msg.to_string());
+
+        assert_eq!(read(&bytes[..10]), bad("truncated header"));
+        assert_eq!(read(&bytes[..bytes.len() - 1]), bad("wrong payload size"));
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(read(&longer), bad("wrong payload size"));
+
+        let mut corrupt = bytes.clone();
+        corrupt[1000] ^= 1;
+        assert_eq!(read(&corrupt), bad("checksum mismatch"));
+        let mut corrupt = bytes.clone();
+        corrupt[0] = b'X';
+        assert_eq!(read(&corrupt), bad("not a lookup table"));
+        let mut corrupt = bytes.clone();
+        corrupt[4] = 2;
+        assert_eq!(read(&corrupt), bad("unsupported version 2"));
+        let mut corrupt = bytes;
+        corrupt[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(read(&corrupt), bad("bad table lengths"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Where hands stand among all the hole cards possible on a board.
+
+use std::cmp::Ordering;
+
+use crate::{card::Card, error::Error, holdem::check_duplicates, HoldemHand, Rank};
+
+/// The best hand possible on a board, see `nuts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NutsResult {
+    pub rank: Rank,
+    /// The hole cards making it, higher card first, strongest combo first.
+    pub combos: Vec<[Card; 2]>,
+    /// The best rank below it; `None` when every combo plays the board,
+    /// as with four aces and a king.
+    pub second: Option<Rank>,
+}
+
+/// The nuts on a board of three to five cards, searching every pair of
+/// unseen hole cards.
+///
+/// ```
+/// use poker::{card::Card, nuts::nuts, Rank};
+///
+/// let result = nuts(&Card::parse_many("As Ks Qs").unwrap()).unwrap();
+/// assert_eq!(result.rank, Rank::RoyalStraightFlush);
+/// let js_ts = Card::parse_many("Js Ts").unwrap();
+/// assert_eq!(result.combos, [[js_ts[0], js_ts[1]]]);
+/// ```
+pub fn nuts(board: &[Card]) -> Result<NutsResult, Error> {
+    let mut best: Option<NutsResult> = None;
+    for (combo, rank) in ranked_combos(board, &[])? {
+        match &mut best {
+            Some(best) if rank == best.rank => best.combos.push(combo),
+            Some(best) if rank < best.rank => best.second = best.second.max(Some(rank)),
+            _ => {
+                best = Some(NutsResult {
+                    rank,
+                    combos: vec![combo],
+                    second: best.map(|b| b.rank),
+                })
+            }
+        }
+    }
+    // a board leaves 47 or more cards, so there is a combo
+    let mut best = best.expect("no hole cards left");
+    best.combos.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(best)
+}
+
+/// How one hand does against every other hole cards on a board, see
+/// `hand_vs_all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NutIndex {
+    pub rank: Rank,
+    pub ahead: usize,
+    pub tied: usize,
+    pub behind: usize,
+    /// 1 for the nuts, 3 when two better ranks are possible.
+    pub position: usize,
+    /// The combos beating the hand, strongest first.
+    pub beaten_by: Vec<[Card; 2]>,
+}
+
+impl NutIndex {
+    pub fn total(&self) -> usize {
+        self.ahead + self.tied + self.behind
+    }
+
+    pub fn is_nuts(&self) -> bool {
+        self.ahead == 0
+    }
+
+    /// The share of combos the hand beats, ties counting half.
+    pub fn percentile(&self) -> f64 {
+        (self.behind as f64 + self.tied as f64 / 2.0) / self.total() as f64
+    }
+}
+
+/// Compares `hole` on a board of three to five cards with every pair of
+/// cards left.
+///
+/// ```
+/// use poker::{card::Card, nuts::hand_vs_all};
+///
+/// let cards = Card::parse_many("As Qs Ks 9s 4s 2d 7h").unwrap();
+/// let index = hand_vs_all([cards[0], cards[1]], &cards[2..]).unwrap();
+/// assert!(index.is_nuts());
+/// assert_eq!(index.total(), 45 * 44 / 2);
+/// ```
+pub fn hand_vs_all(hole: [Card; 2], board: &[Card]) -> Result<NutIndex, Error> {
+    let others = ranked_combos(board, &hole)?;
+    let rank = HoldemHand::best_from(&[&hole[..], board].concat())?.rank();
+
+    let (mut tied, mut behind) = (0, 0);
+    let mut ahead = Vec::new();
+    for (combo, other) in others {
+        match other.cmp(&rank) {
+            Ordering::Greater => ahead.push((other, combo)),
+            Ordering::Equal => tied += 1,
+            Ordering::Less => behind += 1,
+        }
+    }
+    ahead.sort_unstable_by(|a, b| b.cmp(a));
+    let mut better: Vec<Rank> = ahead.iter().map(|&(rank, _)| rank).collect();
+    better.dedup();
+    Ok(NutIndex {
+        rank,
+        ahead: ahead.len(),
+        tied,
+        behind,
+        position: better.len() + 1,
+        beaten_by: ahead.into_iter().map(|(_, combo)| combo).collect(),
+    })
+}
+
+// every pair of cards not on the board or in `dead`, higher card first,
+// with its rank on the board
+fn ranked_combos(board: &[Card], dead: &[Card]) -> Result<Vec<([Card; 2], Rank)>, Error> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(Error::BadCard(format!(
+            "invalid board of {} cards",
+            board.len()
+        )));
+    }
+    check_duplicates(&[dead, board].concat())?;
+
+    let unseen: Vec<Card> = Card::all()
+        .filter(|c| !board.contains(c) && !dead.contains(c))
+        .collect();
+    let mut cards = board.to_vec();
+    let mut ranked = Vec::with_capacity(unseen.len() * (unseen.len() - 1) / 2);
+    for (i, &high) in unseen.iter().enumerate() {
+        for &low in &unseen[..i] {
+            cards.truncate(board.len());
+            cards.extend([high, low]);
+            ranked.push(([high, low], HoldemHand::best_from(&cards)?.rank()));
+        }
+    }
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Value::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        Card::parse_many(s).unwrap()
+    }
+
+    fn combos(s: &str) -> Vec<[Card; 2]> {
+        cards(s).chunks(2).map(|c| [c[0], c[1]]).collect()
+    }
+
+    #[test]
+    fn test_nuts() {
+        let royal = nuts(&cards("As Ks Qs")).unwrap();
+        assert_eq!(royal.rank, Rank::RoyalStraightFlush);
+        assert_eq!(royal.combos, combos("Js Ts"));
+        assert_eq!(
+            royal.second,
+            Some(Rank::Flush([Ace, King, Queen, Jack, Nine]))
+        );
+
+        let quads = nuts(&cards("7h 7d 2c 2s Kd")).unwrap();
+        assert_eq!(quads.rank, Rank::Bomb([Seven, King]));
+        assert_eq!(quads.combos, combos("7s 7c"));
+        assert_eq!(quads.second, Some(Rank::Bomb([Two, King])));
+
+        // the second card of the flush breaks ties too
+        let flush = nuts(&cards("Ks 9s 4s 2d 7h")).unwrap();
+        assert_eq!(flush.rank, Rank::Flush([Ace, King, Queen, Nine, Four]));
+        assert_eq!(flush.combos, combos("As Qs"));
+        assert_eq!(
+            flush.second,
+            Some(Rank::Flush([Ace, King, Jack, Nine, Four]))
+        );
+
+        let sets = nuts(&cards("Kh 9d 4c")).unwrap();
+        assert_eq!(sets.rank, Rank::Set([King, Nine, Four]));
+        assert_eq!(sets.combos, combos("Ks Kc Ks Kd Kc Kd"));
+        assert_eq!(sets.second, Some(Rank::Set([Nine, King, Four])));
+
+        // every combo plays the board
+        let board = nuts(&cards("As Ah Ad Ac Kd")).unwrap();
+        assert_eq!(board.rank, Rank::Bomb([Ace, King]));
+        assert_eq!(board.combos.len(), 47 * 46 / 2);
+        assert_eq!(board.second, None);
+
+        assert!(nuts(&cards("As Ks")).is_err());
+        assert!(nuts(&cards("As Ks As")).is_err());
+    }
+
+    #[test]
+    fn test_hand_vs_all() {
+        let index = |hole: &str, board: &str| {
+            let hole = cards(hole);
+            hand_vs_all([hole[0], hole[1]], &cards(board)).unwrap()
+        };
+        // no straight flush on an unpaired board with three spades
+        let nut_flush = index("As Qs", "Ks 9s 4s 2d 7h");
+        assert_eq!((nut_flush.ahead, nut_flush.tied), (0, 0));
+        assert_eq!(nut_flush.position, 1);
+        assert!(nut_flush.beaten_by.is_empty());
+        assert_eq!(nut_flush.percentile(), 1.0);
+
+        // each ace-high flush ranks apart
+        let queen = index("Qs Js", "Ks 9s 4s 2d 7h");
+        assert_eq!(queen.position, 8);
+        let aces = combos("As Ts As 8s As 7s As 6s As 5s As 3s As 2s");
+        assert_eq!(queen.beaten_by, aces);
+
+        // middle pair against a brute force with the table evaluator
+        let (hole, board) = (cards("9c 8c"), cards("Kh 9d 4s 2c 7h"));
+        let middle = index("9c 8c", "Kh 9d 4s 2c 7h");
+        let rank_of = |a: Card, b: Card| {
+            let seven = [board[0], board[1], board[2], board[3], board[4], a, b];
+            Rank::from_class_index(crate::fast::eval7(&seven)).unwrap()
+        };
+        let mine = rank_of(hole[0], hole[1]);
+        assert_eq!(middle.rank, mine);
+        let left: Vec<Card> = Card::all()
+            .filter(|c| !hole.contains(c) && !board.contains(c))
+            .collect();
+        let (mut ahead, mut tied, mut behind) = (0, 0, 0);
+        for (i, &a) in left.iter().enumerate() {
+            for &b in &left[i + 1..] {
+                match rank_of(a, b).cmp(&mine) {
+                    Ordering::Greater => ahead += 1,
+                    Ordering::Equal => tied += 1,
+                    Ordering::Less => behind += 1,
+                }
+            }
+        }
+        assert_eq!(
+            (middle.ahead, middle.tied, middle.behind),
+            (ahead, tied, behind)
+        );
+        assert_eq!(middle.beaten_by.len(), ahead);
+        assert_eq!(middle.total(), 990);
+        assert!(middle.position > 1 && middle.percentile() < 1.0);
+        // the two sets of kings beat it first
+        assert_eq!(middle.beaten_by[..3], combos("Ks Kc Ks Kd Kc Kd"));
+    }
+}
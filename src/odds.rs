@@ -0,0 +1,193 @@
+//! Pot odds, and the equity a call or a draw needs.
+//!
+//! Chip amounts are `u64` and summed as `f64`, so no stack size can
+//! overflow them.
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::error::Error;
+
+/// The equity calling `to_call` needs to break even, `pot` counting the
+/// bet being called: `to_call / (pot + to_call)`. Nothing to call needs
+/// nothing.
+///
+/// ```
+/// use poker::odds::pot_odds;
+///
+/// // a pot of 100 and a bet of 50 into it
+/// assert_eq!(pot_odds(100 + 50, 50), 0.25);
+/// ```
+pub fn pot_odds(pot: u64, to_call: u64) -> f64 {
+    implied_odds(pot, to_call, 0)
+}
+
+/// `pot_odds` counting `future` chips expected to be won on later
+/// streets when the call gets there.
+pub fn implied_odds(pot: u64, to_call: u64, future: u64) -> f64 {
+    if to_call == 0 {
+        return 0.0;
+    }
+    to_call as f64 / (pot as f64 + to_call as f64 + future as f64)
+}
+
+/// Odds against something, as `x:1`: 3.5:1 against happens 2 times in 9.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Odds(f64);
+
+impl Odds {
+    /// `against:on`, both finite, `on` above zero.
+    pub fn new(against: f64, on: f64) -> Result<Odds, Error> {
+        let odds = against / on;
+        if !(odds.is_finite() && odds >= 0.0 && on > 0.0) {
+            return Err(Error::BadOdds(format!("{}:{}", against, on)));
+        }
+        Ok(Odds(odds))
+    }
+
+    /// The odds against something with `equity`, above 0 and at most 1.
+    pub fn from_equity(equity: f64) -> Result<Odds, Error> {
+        if !(equity > 0.0 && equity <= 1.0) {
+            return Err(Error::BadOdds(format!("equity of {}", equity)));
+        }
+        Ok(Odds((1.0 - equity) / equity))
+    }
+
+    /// The `x` of `x:1`.
+    pub fn against(&self) -> f64 {
+        self.0
+    }
+
+    pub fn equity(&self) -> f64 {
+        1.0 / (self.0 + 1.0)
+    }
+
+    /// `equity` as a percentage to one decimal place, like `22.2%`.
+    pub fn format_percent(&self) -> String {
+        format!("{}%", round_to(self.equity() * 100.0, 1))
+    }
+}
+
+/// Parses `x:y` odds against, or a percentage chance like `25%`.
+impl FromStr for Odds {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || Error::BadOdds(s.to_string());
+        let number = |n: &str| n.trim().parse::<f64>().map_err(|_| bad());
+        if let Some((against, on)) = s.split_once(':') {
+            Odds::new(number(against)?, number(on)?).map_err(|_| bad())
+        } else if let Some(percent) = s.trim().strip_suffix('%') {
+            Odds::from_equity(number(percent)? / 100.0).map_err(|_| bad())
+        } else {
+            Err(bad())
+        }
+    }
+}
+
+/// Writes `x:1`, `x` to two decimal places.
+impl Display for Odds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}:1", round_to(self.0, 2)))
+    }
+}
+
+/// The exact chance that one of `outs` comes on the next `streets`
+/// cards, from the flop (two streets, 47 unseen cards) or the turn (one,
+/// 46 unseen).
+///
+/// ```
+/// use poker::odds::outs_to_equity;
+///
+/// assert_eq!(outs_to_equity(9, 1), Ok(9.0 / 46.0));
+/// ```
+pub fn outs_to_equity(outs: u8, streets: u8) -> Result<f64, Error> {
+    if streets > 2 {
+        return Err(Error::BadOdds(format!("{} streets to come", streets)));
+    }
+    let unseen = 45 + u64::from(streets);
+    if u64::from(outs) > unseen {
+        return Err(Error::BadOdds(format!(
+            "{} outs in {} unseen cards",
+            outs, unseen
+        )));
+    }
+    // ordered runouts of `streets` cards, and those missing every out
+    let runouts = |n: u64| (0..u64::from(streets)).fold(1, |acc, i| acc * (n - i));
+    let total = runouts(unseen);
+    let misses = runouts(unseen - u64::from(outs));
+    Ok((total - misses) as f64 / total as f64)
+}
+
+/// The rule of 2 and 4: two percent per out for each street to come,
+/// capped at 100%. Close to `outs_to_equity` for small numbers of outs.
+pub fn rule_of_two_and_four(outs: u8, streets: u8) -> f64 {
+    (f64::from(outs) * 2.0 * f64::from(streets) / 100.0).min(1.0)
+}
+
+fn round_to(x: f64, places: i32) -> f64 {
+    let scale = 10f64.powi(places);
+    (x * scale).round() / scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pot_odds() {
+        assert_eq!(pot_odds(150, 50), 0.25);
+        assert_eq!(pot_odds(100, 100), 0.5);
+        assert_eq!(pot_odds(100, 0), 0.0);
+        // calling into an empty pot needs to win every time
+        assert_eq!(pot_odds(0, 50), 1.0);
+        assert_eq!(pot_odds(u64::MAX, u64::MAX), 0.5);
+
+        assert_eq!(implied_odds(150, 50, 0), 0.25);
+        assert_eq!(implied_odds(150, 50, 200), 0.125);
+        assert_eq!(implied_odds(0, 0, 100), 0.0);
+    }
+
+    #[test]
+    fn test_odds() {
+        let odds: Odds = "3.5:1".parse().unwrap();
+        assert_eq!(odds.against(), 3.5);
+        assert_eq!(odds.equity(), 2.0 / 9.0);
+        assert_eq!(odds.format_percent(), "22.2%");
+        assert_eq!(odds.to_string(), "3.5:1");
+        assert_eq!("7:2".parse(), Ok(odds));
+
+        let odds: Odds = "25%".parse().unwrap();
+        assert_eq!(odds.to_string(), "3:1");
+        assert_eq!(Odds::from_equity(0.25), Ok(odds));
+        assert_eq!(Odds::from_equity(1.0).unwrap().to_string(), "0:1");
+        assert_eq!(Odds::from_equity(0.6).unwrap().to_string(), "0.67:1");
+        assert_eq!(format!("{:>6}", odds), "   3:1");
+
+        for bad in ["", "3", "3:0", "-1:1", "x:1", "0%", "101%"] {
+            assert_eq!(bad.parse::<Odds>(), Err(Error::BadOdds(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_outs() {
+        assert_eq!(outs_to_equity(9, 1), Ok(9.0 / 46.0));
+        // missing both cards is 38/47 * 37/46
+        let flop = outs_to_equity(9, 2).unwrap();
+        assert_eq!(flop, (47.0 * 46.0 - 38.0 * 37.0) / (47.0 * 46.0));
+        assert!((flop - 0.35).abs() < 0.001);
+        assert_eq!(outs_to_equity(9, 0), Ok(0.0));
+        assert_eq!(outs_to_equity(0, 2), Ok(0.0));
+        assert_eq!(outs_to_equity(46, 1), Ok(1.0));
+        assert!(outs_to_equity(47, 1).is_err());
+        assert!(outs_to_equity(1, 3).is_err());
+
+        assert_eq!(rule_of_two_and_four(9, 1), 0.18);
+        assert_eq!(rule_of_two_and_four(9, 2), 0.36);
+        assert_eq!(rule_of_two_and_four(30, 2), 1.0);
+        // the rule overestimates big draws
+        assert!(rule_of_two_and_four(15, 2) > outs_to_equity(15, 2).unwrap());
+    }
+}
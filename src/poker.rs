@@ -8,19 +8,30 @@ struct Pack {
 impl Pack {
     fn default() -> Self {
         Pack {
-            values: Value::values().into(),
-            suits: Suit::values().into(),
+            values: Value::ALL.into(),
+            suits: Suit::ALL.into(),
             jokers: Some(vec![Joker::Big, Joker::Small]),
         }
     }
-}
 
-trait Poker {
-    
+    fn cards(&self) -> Vec<CardKind> {
+        let mut cards: Vec<CardKind> = self
+            .suits
+            .iter()
+            .flat_map(|&s| self.values.iter().map(move |&v| Card::new(s, v).into()))
+            .collect();
+        if let Some(jokers) = &self.jokers {
+            cards.extend(jokers.iter().map(|&j| CardKind::from(j)));
+        }
+        cards
+    }
 }
 
-trait Rank {
-    fn rank(&self) -> u8;
+trait Poker {}
+
+/// A hand strength as a single number, higher is better.
+pub trait Rank {
+    fn rank(&self) -> u32;
 }
 
 #[cfg(test)]
@@ -34,4 +45,27 @@ mod tests {
         assert_eq!(pack.suits.len(), 4);
         assert_eq!(pack.jokers.unwrap().len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pack_cards() {
+        let cards = Pack::default().cards();
+        assert_eq!(cards.len(), 54);
+        assert_eq!(cards.iter().filter(|c| c.is_joker()).count(), 2);
+        assert!(cards.contains(&CardKind::Joker(Joker::Big)));
+        assert!(cards.contains(&CardKind::Joker(Joker::Small)));
+        assert_eq!(cards.iter().max(), Some(&CardKind::Joker(Joker::Big)));
+
+        let printed = cards
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(printed.starts_with("2h 3h 4h"));
+        assert!(printed.ends_with("As bj sj"));
+        let parsed: Vec<CardKind> = printed
+            .split(' ')
+            .map(|s| CardKind::try_from(s).unwrap())
+            .collect();
+        assert_eq!(parsed, cards);
+    }
+}
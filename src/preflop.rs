@@ -0,0 +1,731 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::{
+    canonical::canonicalize,
+    card::{Card, Suit, Value},
+    equity::{equity_exhaustive, Equity},
+    error::Error,
+    lookup::checksum,
+};
+
+// every class by all-in equity against one random hand, strongest first;
+// Monte Carlo estimates over 4M deals per class, from 85.2% for AA down
+// to 32.3% for 32o
+#[rustfmt::skip]
+const BY_EQUITY: [&str; 169] = [
+    "AA", "KK", "QQ", "JJ", "TT", "99", "88", "AKs", "77", "AQs", "AJs", "AKo",
+    "ATs", "AQo", "AJo", "KQs", "66", "A9s", "ATo", "KJs", "A8s", "KTs", "KQo", "A7s",
+    "A9o", "KJo", "55", "QJs", "K9s", "A5s", "A6s", "A8o", "KTo", "QTs", "A4s", "A7o",
+    "K8s", "A3s", "QJo", "K9o", "A5o", "A6o", "Q9s", "K7s", "JTs", "A2s", "QTo", "44",
+    "A4o", "K6s", "K8o", "Q8s", "A3o", "K5s", "J9s", "Q9o", "JTo", "K7o", "K4s", "A2o",
+    "Q7s", "K6o", "K3s", "J8s", "T9s", "33", "Q8o", "Q6s", "K5o", "J9o", "K2s", "Q5s",
+    "T8s", "K4o", "J7s", "Q4s", "Q7o", "T9o", "J8o", "K3o", "Q6o", "Q3s", "98s", "T7s",
+    "J6s", "K2o", "22", "Q2s", "Q5o", "J5s", "T8o", "J7o", "97s", "Q4o", "J4s", "T6s",
+    "Q3o", "J3s", "98o", "87s", "T7o", "J6o", "96s", "J2s", "Q2o", "T5s", "J5o", "T4s",
+    "97o", "86s", "J4o", "T6o", "T3s", "95s", "76s", "J3o", "87o", "T2s", "85s", "96o",
+    "J2o", "T5o", "94s", "75s", "T4o", "93s", "86o", "65s", "84s", "95o", "T3o", "92s",
+    "76o", "74s", "T2o", "54s", "85o", "64s", "83s", "94o", "75o", "82s", "93o", "73s",
+    "65o", "53s", "63s", "84o", "92o", "43s", "74o", "54o", "72s", "64o", "52s", "62s",
+    "83o", "82o", "42s", "73o", "53o", "63o", "32s", "43o", "72o", "52o", "62o", "42o",
+    "32o",
+];
+
+// Sklansky and Malmuth's starting-hand groups, from Hold'em Poker for
+// Advanced Players
+const SKLANSKY_GROUPS: [&str; 8] = [
+    "AA KK QQ JJ AKs",
+    "TT AQs AJs KQs AKo",
+    "99 JTs QJs KJs ATs AQo",
+    "T9s KQo 88 QTs 98s J9s AJo KTs",
+    "77 87s Q9s T8s KJo QJo JTo 76s 97s A9s A8s A7s A6s A5s A4s A3s A2s 65s",
+    "66 ATo 55 86s KTo QTo 54s K9s J8s 75s",
+    "44 J9o 64s T9o 53s 33 98o 43s 22 K8s K7s K6s K5s K4s K3s K2s T7s Q8s",
+    "87o A9o Q9o 76o 42s 32s 96s 85s J8o J7s 65o 54o 74s K9o T8o",
+];
+
+/// One of the 169 kinds of starting hand: a pair, or two values suited
+/// or offsuit, written `"77"`, `"AKs"` or `"T9o"`.
+///
+/// Classes order strongest first by this simple scheme: pairs above
+/// everything else, then by the higher value, the lower value, and
+/// suited above offsuit. `AA > KK > .. > 22 > AKs > AKo > AQs > .. > 32o`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HoleClass {
+    high: Value,
+    low: Value,
+    suited: bool,
+}
+
+impl HoleClass {
+    pub fn pair(value: Value) -> Self {
+        HoleClass {
+            high: value,
+            low: value,
+            suited: false,
+        }
+    }
+
+    /// Two different values of one suit, in either order.
+    pub fn suited(a: Value, b: Value) -> Result<Self, Error> {
+        Self::unpaired(a, b, true)
+    }
+
+    /// Two different values of different suits, in either order.
+    pub fn offsuit(a: Value, b: Value) -> Result<Self, Error> {
+        Self::unpaired(a, b, false)
+    }
+
+    fn unpaired(a: Value, b: Value, suited: bool) -> Result<Self, Error> {
+        if a == b {
+            let suffix = if suited { 's' } else { 'o' };
+            let class = format!("{}{}{}", a.to_char(), b.to_char(), suffix);
+            return Err(Error::BadRange(class));
+        }
+        Ok(HoleClass {
+            high: a.max(b),
+            low: a.min(b),
+            suited,
+        })
+    }
+
+    /// The class of two hole cards.
+    pub fn from_cards([a, b]: [Card; 2]) -> Self {
+        if a.value() == b.value() {
+            return Self::pair(a.value());
+        }
+        HoleClass {
+            high: a.value().max(b.value()),
+            low: a.value().min(b.value()),
+            suited: a.suit() == b.suit(),
+        }
+    }
+
+    /// All 169 classes, strongest first.
+    pub fn all() -> impl Iterator<Item = HoleClass> {
+        let pairs = Value::ALL.into_iter().rev().map(Self::pair);
+        let unpaired = Value::ALL.into_iter().rev().flat_map(|high| {
+            Value::range_exclusive(Value::Two, high)
+                .rev()
+                .flat_map(move |low| [true, false].map(|suited| HoleClass { high, low, suited }))
+        });
+        pairs.chain(unpaired)
+    }
+
+    /// All 169 classes, strongest first by `preflop_rank`.
+    pub fn by_preflop_rank() -> impl Iterator<Item = HoleClass> {
+        ranking().iter().copied()
+    }
+
+    /// Place in the preflop ranking, from 0 for `AA` to 168 for `32o`.
+    ///
+    /// Classes rank by their all-in equity against a random hand. The
+    /// equities are precomputed estimates, so classes within a few
+    /// hundredths of a percent of each other may be in either order.
+    pub fn preflop_rank(&self) -> u8 {
+        ranking()
+            .iter()
+            .position(|c| c == self)
+            .unwrap_or(u8::MAX as usize) as u8
+    }
+
+    /// The class's Sklansky-Malmuth group, from 1 for the strongest hands
+    /// (`AA KK QQ JJ AKs`) to 8, or 0 for the hands outside the eight
+    /// groups, which are not worth playing.
+    pub fn sklansky_group(&self) -> u8 {
+        static GROUPS: OnceLock<Vec<u8>> = OnceLock::new();
+        let groups = GROUPS.get_or_init(|| {
+            let mut groups = vec![0; 169];
+            for (group, classes) in (1..).zip(SKLANSKY_GROUPS) {
+                for class in classes
+                    .split_whitespace()
+                    .filter_map(|s| s.parse::<HoleClass>().ok())
+                {
+                    groups[class.index()] = group;
+                }
+            }
+            groups
+        });
+        groups[self.index()]
+    }
+
+    /// The classes of one `sklansky_group`, strongest first.
+    pub fn in_group(group: u8) -> impl Iterator<Item = HoleClass> {
+        Self::all().filter(move |c| c.sklansky_group() == group)
+    }
+
+    /// Bill Chen's quick score for a starting hand, from -1 for the worst
+    /// (`72o`) to 20 for `AA`.
+    ///
+    /// The published rules:
+    /// - the higher card scores 10 for an ace, 8 for a king, 7 for a queen,
+    ///   6 for a jack and half its value otherwise
+    /// - a pair doubles that, to at least 5
+    /// - suited cards add 2
+    /// - a gap between the values takes 1 for one card missing between
+    ///   them, 2 for two, 4 for three and 5 for more (the ace counts high)
+    /// - connected or one-gapped cards both below a queen add 1
+    /// - half points round up
+    pub fn chen_score(&self) -> f64 {
+        let points = |v: Value| match v {
+            Value::Ace => 10.0,
+            Value::King => 8.0,
+            Value::Queen => 7.0,
+            Value::Jack => 6.0,
+            other => f64::from(other.value()) / 2.0,
+        };
+        if self.is_pair() {
+            return (points(self.high) * 2.0).max(5.0).ceil();
+        }
+        let mut score = points(self.high);
+        if self.suited {
+            score += 2.0;
+        }
+        let gap = self.high.gap(self.low) - 1;
+        score -= match gap {
+            0 => 0.0,
+            1 => 1.0,
+            2 => 2.0,
+            3 => 4.0,
+            _ => 5.0,
+        };
+        if gap <= 1 && self.high < Value::Queen {
+            score += 1.0;
+        }
+        score.ceil()
+    }
+
+    // position in `all`
+    fn index(&self) -> usize {
+        // 0 for an ace down to 12 for a two
+        let below_ace = |v: Value| (Value::Ace.value() - v.value()) as usize;
+        let (high, low) = (below_ace(self.high), below_ace(self.low));
+        if self.is_pair() {
+            return high;
+        }
+        // the 13 pairs, then a suited and an offsuit class for each kicker
+        // of every higher top card
+        let earlier: usize = (0..high).map(|h| 2 * (12 - h)).sum();
+        13 + earlier + 2 * (low - high - 1) + usize::from(!self.suited)
+    }
+
+    pub fn high(&self) -> Value {
+        self.high
+    }
+
+    pub fn low(&self) -> Value {
+        self.low
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    pub fn is_suited(&self) -> bool {
+        self.suited
+    }
+
+    /// 6 for a pair, 4 suited, 12 offsuit.
+    pub fn combo_count(&self) -> usize {
+        match (self.is_pair(), self.suited) {
+            (true, _) => 6,
+            (false, true) => 4,
+            (false, false) => 12,
+        }
+    }
+
+    /// Every pair of cards in the class, higher card first.
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::with_capacity(self.combo_count());
+        for (i, &a) in Suit::ALL.iter().enumerate() {
+            for (j, &b) in Suit::ALL.iter().enumerate() {
+                let wanted = match (self.is_pair(), self.suited) {
+                    (true, _) => i < j,
+                    (false, true) => i == j,
+                    (false, false) => i != j,
+                };
+                if wanted {
+                    let (x, y) = (Card::new(a, self.high), Card::new(b, self.low));
+                    combos.push([x.max(y), x.min(y)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+fn ranking() -> &'static [HoleClass] {
+    static RANKING: OnceLock<Vec<HoleClass>> = OnceLock::new();
+    RANKING.get_or_init(|| BY_EQUITY.iter().filter_map(|s| s.parse().ok()).collect())
+}
+
+impl Ord for HoleClass {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |c: &HoleClass| (c.is_pair(), c.high, c.low, c.suited);
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for HoleClass {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for HoleClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let suffix = match (self.is_pair(), self.suited) {
+            (true, _) => "",
+            (false, true) => "s",
+            (false, false) => "o",
+        };
+        let class = format!("{}{}{}", self.high.to_char(), self.low.to_char(), suffix);
+        f.pad(&class)
+    }
+}
+
+impl TryFrom<&str> for HoleClass {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let bad = || Error::BadRange(s.to_string());
+        let chars: Vec<char> = s.chars().collect();
+        let (a, b) = match chars[..] {
+            [a, b] | [a, b, _] => (
+                Value::try_from(a).map_err(|_| bad())?,
+                Value::try_from(b).map_err(|_| bad())?,
+            ),
+            _ => return Err(bad()),
+        };
+        match (chars.get(2), a == b) {
+            (None, true) => Ok(Self::pair(a)),
+            (Some('s'), false) => Self::suited(a, b),
+            (Some('o'), false) => Self::offsuit(a, b),
+            _ => Err(bad()),
+        }
+    }
+}
+
+impl FromStr for HoleClass {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// `HoleClass::chen_score` of the hand's class.
+pub fn chen_score(cards: [Card; 2]) -> f64 {
+    HoleClass::from_cards(cards).chen_score()
+}
+
+/// Exact all-in equity of `a` against `b` before the flop.
+///
+/// Every pair of an `a` combo and a `b` combo sharing no card deals the
+/// same boards, so the runouts of all such pairs are summed: `AKs` against
+/// `AKo` plays 24 of the 48 pairs of combos, and `AA` against `AA` only 6.
+/// Pairs alike up to suits, such as `AhAd` against `KhKd` and `AsAc`
+/// against `KsKc`, are enumerated once.
+///
+/// Each matchup takes up to a few enumerations of 1.7M boards the first
+/// time and is then kept for the process; `MatchupTable` has them all.
+///
+/// ```no_run
+/// use poker::preflop::{matchup_equity, HoleClass};
+///
+/// let aces: HoleClass = "AA".parse().unwrap();
+/// let kings: HoleClass = "KK".parse().unwrap();
+/// assert!((matchup_equity(aces, kings).equity() - 0.82).abs() < 0.01);
+/// ```
+pub fn matchup_equity(a: HoleClass, b: HoleClass) -> Equity {
+    static MATCHUPS: OnceLock<Mutex<HashMap<(HoleClass, HoleClass), Equity>>> = OnceLock::new();
+    let matchups = MATCHUPS.get_or_init(Default::default);
+    let known = |matchups: &HashMap<_, Equity>| match matchups.get(&(a, b)) {
+        Some(&equity) => Some(equity),
+        None => matchups.get(&(b, a)).map(reversed),
+    };
+    if let Some(equity) = known(&matchups.lock().unwrap_or_else(|e| e.into_inner())) {
+        return equity;
+    }
+    // computed unlocked, another thread may compute the same matchup
+    let equity = exact_matchup(a, b);
+    let mut matchups = matchups.lock().unwrap_or_else(|e| e.into_inner());
+    matchups.insert((a, b), equity);
+    equity
+}
+
+fn exact_matchup(a: HoleClass, b: HoleClass) -> Equity {
+    // each pattern of suits once, with a representative and how often
+    // it occurs
+    let mut patterns: HashMap<Vec<Card>, ([Card; 2], [Card; 2], u64)> = HashMap::new();
+    for hero in a.combos() {
+        for villain in b.combos() {
+            if hero.iter().any(|c| villain.contains(c)) {
+                continue;
+            }
+            let entry = patterns.entry(suit_pattern(hero, villain));
+            entry.or_insert((hero, villain, 0)).2 += 1;
+        }
+    }
+    let mut total = Equity::default();
+    for (hero, villain, count) in patterns.into_values() {
+        let equity = equity_exhaustive(hero, villain, &[]).expect("combos share no card");
+        total += Equity {
+            wins: equity.wins * count,
+            ties: equity.ties * count,
+            losses: equity.losses * count,
+            tie_share: equity.tie_share * count as f64,
+        };
+    }
+    total
+}
+
+// the same for any relabelling of the suits: cards of one value may be
+// listed in either order, so the smallest canonical form over both
+fn suit_pattern(hero: [Card; 2], villain: [Card; 2]) -> Vec<Card> {
+    let orders = |[x, y]: [Card; 2]| {
+        if x.value() == y.value() {
+            vec![[x, y], [y, x]]
+        } else {
+            vec![[x, y]]
+        }
+    };
+    let mut best: Option<Vec<Card>> = None;
+    for h in orders(hero) {
+        for v in orders(villain) {
+            let (mut cards, _) = canonicalize(&[h[0], h[1], v[0], v[1]]);
+            cards[..2].sort_unstable();
+            cards[2..].sort_unstable();
+            if best.as_ref().is_none_or(|best| cards < *best) {
+                best = Some(cards);
+            }
+        }
+    }
+    best.unwrap_or_default()
+}
+
+// the other player's side of a heads-up matchup
+fn reversed(equity: &Equity) -> Equity {
+    Equity {
+        wins: equity.losses,
+        losses: equity.wins,
+        ..*equity
+    }
+}
+
+const MAGIC: &[u8; 4] = b"PKMT";
+const VERSION: u32 = 1;
+const CLASSES: usize = 169;
+
+/// `matchup_equity` for every pair of classes.
+///
+/// ```no_run
+/// use poker::preflop::{HoleClass, MatchupTable};
+///
+/// let table = MatchupTable::generate();
+/// table.save("matchups.table").unwrap();
+/// let table = MatchupTable::load("matchups.table").unwrap();
+/// let (aces, kings) = ("AA".parse().unwrap(), "KK".parse().unwrap());
+/// assert!(table.get(aces, kings).equity() > 0.8);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchupTable {
+    // by the positions of the two classes in `HoleClass::all`
+    equities: Vec<Equity>,
+}
+
+impl MatchupTable {
+    /// Computes every matchup exactly, about 50,000 preflop enumerations.
+    /// That is a long job, spread over threads with the `parallel`
+    /// feature, so `save` the result.
+    pub fn generate() -> MatchupTable {
+        let classes: Vec<HoleClass> = HoleClass::all().collect();
+        let pairs: Vec<(usize, usize)> = (0..CLASSES)
+            .flat_map(|i| (i..CLASSES).map(move |j| (i, j)))
+            .collect();
+        let exact = |&(i, j): &(usize, usize)| exact_matchup(classes[i], classes[j]);
+        #[cfg(feature = "parallel")]
+        let computed: Vec<Equity> = {
+            use rayon::prelude::*;
+            pairs.par_iter().map(exact).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let computed: Vec<Equity> = pairs.iter().map(exact).collect();
+
+        let mut equities = vec![Equity::default(); CLASSES * CLASSES];
+        for (&(i, j), equity) in pairs.iter().zip(computed) {
+            equities[i * CLASSES + j] = equity;
+            equities[j * CLASSES + i] = reversed(&equity);
+        }
+        MatchupTable { equities }
+    }
+
+    pub fn get(&self, a: HoleClass, b: HoleClass) -> Equity {
+        self.equities[a.index() * CLASSES + b.index()]
+    }
+
+    /// Writes the table to `path`, see `write_to`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a table saved by `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<MatchupTable, Error> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes a header (magic, version, checksum) and then the wins,
+    /// ties, losses and tie share of each matchup, little-endian.
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), Error> {
+        let payload = self.payload();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&checksum(&payload).to_le_bytes())?;
+        writer.write_all(&payload)?;
+        Ok(())
+    }
+
+    /// Reads a table written by `write_to`, rejecting anything with the
+    /// wrong magic, version, size or checksum.
+    pub fn read_from(mut reader: impl Read) -> Result<MatchupTable, Error> {
+        let bad = |msg: &str| Error::BadTable(msg.to_string());
+        let mut header = [0; 16];
+        reader
+            .read_exact(&mut header)
+            .map_err(|_| bad("truncated header"))?;
+        if &header[..4] != MAGIC {
+            return Err(bad("not a matchup table"));
+        }
+        let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if version != VERSION {
+            return Err(Error::BadTable(format!("unsupported version {}", version)));
+        }
+        let expected = (CLASSES * CLASSES * 32) as u64;
+        let mut payload = Vec::new();
+        reader.take(expected + 1).read_to_end(&mut payload)?;
+        if payload.len() as u64 != expected {
+            return Err(bad("wrong payload size"));
+        }
+        let mut sum = [0; 8];
+        sum.copy_from_slice(&header[8..]);
+        if checksum(&payload) != u64::from_le_bytes(sum) {
+            return Err(bad("checksum mismatch"));
+        }
+
+        let word = |b: &[u8]| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+        let equities = payload
+            .chunks_exact(32)
+            .map(|b| Equity {
+                wins: word(&b[..8]),
+                ties: word(&b[8..16]),
+                losses: word(&b[16..24]),
+                tie_share: f64::from_bits(word(&b[24..])),
+            })
+            .collect();
+        Ok(MatchupTable { equities })
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let fields = |e: &Equity| [e.wins, e.ties, e.losses, e.tie_share.to_bits()];
+        self.equities
+            .iter()
+            .flat_map(fields)
+            .flat_map(u64::to_le_bytes)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(s: &str) -> HoleClass {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(class("AKs"), class("KAs"));
+        assert_eq!(class("AKs").to_string(), "AKs");
+        assert_eq!(class("9To").to_string(), "T9o");
+        assert_eq!(class("77").to_string(), "77");
+        assert_eq!(format!("{:>4}", class("77")), "  77");
+        for bad in ["AAs", "AAo", "AK", "AKx", "A", "AKso", "1Ks", "AK "] {
+            assert_eq!(
+                bad.parse::<HoleClass>(),
+                Err(Error::BadRange(bad.to_string()))
+            );
+        }
+        assert!(HoleClass::suited(Value::Ace, Value::Ace).is_err());
+    }
+
+    #[test]
+    fn test_from_cards() {
+        let cards = |s: &str| -> [Card; 2] { Card::parse_many(s).unwrap().try_into().unwrap() };
+        assert_eq!(HoleClass::from_cards(cards("Kh As")), class("AKo"));
+        assert_eq!(HoleClass::from_cards(cards("Kh Ah")), class("AKs"));
+        assert_eq!(HoleClass::from_cards(cards("7h 7c")), class("77"));
+    }
+
+    #[test]
+    fn test_combos() {
+        let classes: Vec<HoleClass> = HoleClass::all().collect();
+        assert_eq!(classes.len(), 169);
+        assert!(classes.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(classes[0], class("AA"));
+        assert_eq!(classes[13], class("AKs"));
+        assert_eq!(classes[168], class("32o"));
+
+        let mut all = Vec::new();
+        for c in &classes {
+            let combos = c.combos();
+            assert_eq!(combos.len(), c.combo_count());
+            assert!(combos
+                .iter()
+                .all(|&combo| HoleClass::from_cards(combo) == *c));
+            all.extend(combos);
+        }
+        assert_eq!(all.len(), 1326);
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 1326);
+        assert_eq!(class("QQ").combos().len(), 6);
+        assert_eq!(class("T9s").combos().len(), 4);
+        assert_eq!(class("T9o").combos().len(), 12);
+    }
+
+    #[test]
+    fn test_preflop_rank() {
+        assert_eq!(class("AA").preflop_rank(), 0);
+        assert_eq!(class("KK").preflop_rank(), 1);
+        assert_eq!(class("32o").preflop_rank(), 168);
+        assert!(class("AKs").preflop_rank() < class("AKo").preflop_rank());
+        assert!(class("22").preflop_rank() > class("K2s").preflop_rank());
+
+        let mut ranks: Vec<u8> = HoleClass::all().map(|c| c.preflop_rank()).collect();
+        ranks.sort();
+        assert!(ranks.iter().copied().eq(0..169));
+        let ranked: Vec<HoleClass> = HoleClass::by_preflop_rank().collect();
+        assert!(ranked
+            .iter()
+            .enumerate()
+            .all(|(i, c)| c.preflop_rank() as usize == i));
+    }
+
+    #[test]
+    fn test_matchup() {
+        let equity = |a: &str, b: &str| matchup_equity(class(a), class(b));
+        let aces_kings = equity("AA", "KK");
+        assert_eq!(aces_kings.total(), 36 * 1_712_304);
+        assert!(
+            (aces_kings.equity() - 0.8195).abs() < 0.0005,
+            "{}",
+            aces_kings.equity()
+        );
+        assert_eq!(equity("KK", "AA"), reversed(&aces_kings));
+
+        let flip = equity("AKs", "22").equity();
+        assert!((flip - 0.4989).abs() < 0.0005, "{}", flip);
+
+        // only the aces of the other two suits are left
+        let mirror = equity("AA", "AA");
+        assert_eq!(mirror.total(), 6 * 1_712_304);
+        assert_eq!(mirror.wins, mirror.losses);
+    }
+
+    #[test]
+    fn test_matchup_table() {
+        let classes: Vec<HoleClass> = HoleClass::all().collect();
+        assert!(classes.iter().enumerate().all(|(i, c)| c.index() == i));
+
+        let equities = (0..(CLASSES * CLASSES) as u64)
+            .map(|i| Equity {
+                wins: i,
+                ties: 2 * i,
+                losses: 3,
+                tie_share: i as f64,
+            })
+            .collect();
+        let table = MatchupTable { equities };
+        assert_eq!(table.get(class("AA"), class("KK")).wins, 1);
+        assert_eq!(table.get(class("32o"), class("32o")).wins, 169 * 169 - 1);
+
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        assert_eq!(MatchupTable::read_from(&bytes[..]).unwrap(), table);
+        let read = |bytes: &[u8]| MatchupTable::read_from(bytes).unwrap_err();
+        let bad = |msg: &str| Error::BadTable(msg.to_string());
+        assert_eq!(read(&bytes[..10]), bad("truncated header"));
+        assert_eq!(read(&bytes[..bytes.len() - 1]), bad("wrong payload size"));
+        let mut corrupt = bytes.clone();
+        corrupt[100] ^= 1;
+        assert_eq!(read(&corrupt), bad("checksum mismatch"));
+        corrupt[0] = b'X';
+        assert_eq!(read(&corrupt), bad("not a matchup table"));
+    }
+
+    #[test]
+    fn test_chen_score() {
+        let score = |s: &str| class(s).chen_score();
+        assert_eq!(score("AA"), 20.0);
+        assert_eq!(score("KK"), 16.0);
+        assert_eq!(score("22"), 5.0);
+        assert_eq!(score("55"), 5.0);
+        assert_eq!(score("66"), 6.0);
+        assert_eq!(score("AKs"), 12.0);
+        assert_eq!(score("AKo"), 10.0);
+        // the connector bonus, only below a queen
+        assert_eq!(score("JTs"), 9.0);
+        assert_eq!(score("QJs"), 9.0);
+        // 4.5 - 4 rounds up to 1, -1.5 rounds up to -1
+        assert_eq!(score("95o"), 1.0);
+        assert_eq!(score("72o"), -1.0);
+        assert_eq!(score("T9s"), 8.0);
+        assert_eq!(score("A2o"), 5.0);
+
+        let worst = HoleClass::all()
+            .map(|c| c.chen_score())
+            .fold(f64::MAX, f64::min);
+        assert_eq!(worst, score("72o"));
+        let cards: [Card; 2] = Card::parse_many("Ts Js").unwrap().try_into().unwrap();
+        assert_eq!(chen_score(cards), 9.0);
+    }
+
+    #[test]
+    fn test_sklansky_groups() {
+        let group = |g| {
+            HoleClass::in_group(g)
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(group(1), ["AA", "KK", "QQ", "JJ", "AKs"]);
+        assert_eq!(group(2), ["TT", "AKo", "AQs", "AJs", "KQs"]);
+        assert_eq!(class("A5s").sklansky_group(), 5);
+        assert_eq!(class("K2s").sklansky_group(), 7);
+        assert_eq!(class("72o").sklansky_group(), 0);
+
+        // each class is listed once, so the groups hold 169 between them
+        let listed: Vec<&str> = SKLANSKY_GROUPS
+            .iter()
+            .flat_map(|g| g.split_whitespace())
+            .collect();
+        let mut distinct: Vec<HoleClass> = listed.iter().map(|s| class(s)).collect();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), listed.len());
+        let sizes: Vec<usize> = (0..=8).map(|g| HoleClass::in_group(g).count()).collect();
+        assert_eq!(sizes[1..], [5, 5, 6, 8, 18, 10, 18, 15]);
+        assert_eq!(sizes.iter().sum::<usize>(), 169);
+    }
+}
@@ -0,0 +1,83 @@
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+
+use crate::card::{Card, Suit, Value};
+
+impl Distribution<Suit> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Suit {
+        Suit::ALL[rng.gen_range(0..Suit::COUNT)]
+    }
+}
+
+impl Distribution<Value> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Value {
+        Value::ALL[rng.gen_range(0..Value::COUNT)]
+    }
+}
+
+/// Uniform over the 52 standard cards.
+impl Distribution<Card> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Card {
+        Card::ALL[rng.gen_range(0..52)]
+    }
+}
+
+impl Card {
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Card {
+        rng.gen()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // chi-square statistic of the observed counts against a uniform spread
+    fn chi_square(counts: &[u32]) -> f64 {
+        let total: u32 = counts.iter().sum();
+        let expected = total as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn test_card_uniform() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = [0u32; 52];
+        for _ in 0..52_000 {
+            counts[Card::random(&mut rng).to_index() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 0));
+        // 51 degrees of freedom, p = 0.001 critical value is about 87
+        assert!(chi_square(&counts) < 87.0, "{}", chi_square(&counts));
+    }
+
+    #[test]
+    fn test_suit_value_uniform() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut suits = [0u32; 4];
+        let mut values = [0u32; 13];
+        for _ in 0..13_000 {
+            suits[rng.gen::<Suit>() as usize] += 1;
+            values[rng.gen::<Value>().value() as usize - 2] += 1;
+        }
+        // critical values at p = 0.001 for 3 and 12 degrees of freedom
+        assert!(chi_square(&suits) < 16.3);
+        assert!(chi_square(&values) < 32.9);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20).map(|_| Card::random(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(draw(1), draw(1));
+        assert_ne!(draw(1), draw(2));
+    }
+}
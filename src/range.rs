@@ -0,0 +1,639 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+    ops::RangeBounds,
+    str::FromStr,
+};
+
+use crate::{
+    card::{Card, CardSet, Value},
+    error::Error,
+    preflop::HoleClass,
+};
+
+/// A set of two-card starting hands ("combos"), each with a weight: how
+/// often the hand is played this way, from just above 0 to 1.
+///
+/// Each combo is stored higher card first, so `[Kd, As]` and `[As, Kd]`
+/// are the same combo.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    // weights in (0, 1], a combo set to 0 is removed
+    combos: BTreeMap<[Card; 2], f64>,
+}
+
+impl Range {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses comma-separated items in the usual notation:
+    ///
+    /// - classes: `KQo`, `T9s`, `77`, or `AK` for both `AKs` and `AKo`
+    /// - `+` for every better kicker or pair: `ATs+` is `ATs` to `AKs`,
+    ///   `22+` is every pair
+    /// - spans between two classes with the same gap or high card:
+    ///   `65s-87s`, `A2s-A5s`, `22-55`
+    /// - single combos: `AhKh`
+    ///
+    /// Any item may carry a weight in percent, `AQs@50` or `[50]AQs`, and
+    /// is otherwise at full weight. A combo named by several items takes
+    /// the weight of the last. A malformed item fails with
+    /// `Error::BadRange` holding that item.
+    ///
+    /// ```
+    /// use poker::range::Range;
+    ///
+    /// let range = Range::parse("TT+, ATs+, 65s-87s, KQo").unwrap();
+    /// assert_eq!(range.len(), 30 + 16 + 12 + 12);
+    /// assert_eq!(range.to_string(), "TT+, ATs+, KQo, 87s-65s");
+    ///
+    /// let range = Range::parse("QQ+, [50]AKo, AQs@25").unwrap();
+    /// assert_eq!(range.count_combos(&Default::default()), 18.0 + 6.0 + 1.0);
+    /// assert_eq!(range.to_string(), "QQ+, AKo@50, AQs@25");
+    /// ```
+    pub fn parse(s: &str) -> Result<Range, Error> {
+        let mut range = Range::new();
+        for item in s.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let bad = || Error::BadRange(item.to_string());
+            let (body, weight) = split_weight(item).ok_or_else(bad)?;
+            for combo in parse_item(body).ok_or_else(bad)? {
+                range.set_combo_weight(combo, weight);
+            }
+        }
+        Ok(range)
+    }
+
+    /// The strongest classes by `HoleClass::preflop_rank` that make up
+    /// about `percent`% of the 1326 combos.
+    ///
+    /// Classes are taken in rank order while each brings the combo count
+    /// closer to the target, a class landing exactly halfway is taken, so
+    /// a larger percentage always gives a superset.
+    pub fn top_percent(percent: f64) -> Range {
+        let percent = if percent.is_nan() {
+            0.0
+        } else {
+            percent.clamp(0.0, 100.0)
+        };
+        let target = percent / 100.0 * 1326.0;
+        let mut range = Range::new();
+        for class in HoleClass::by_preflop_rank() {
+            if range.len() as f64 + class.combo_count() as f64 / 2.0 > target {
+                break;
+            }
+            range.extend(class.combos());
+        }
+        range
+    }
+
+    /// Every combo of the classes in the given `HoleClass::sklansky_group`
+    /// groups, e.g. `1..=3`. Group 0, the unplayable hands, is included
+    /// only if the bounds take it in.
+    pub fn from_sklansky_groups(groups: impl RangeBounds<u8>) -> Range {
+        HoleClass::all()
+            .filter(|class| groups.contains(&class.sklansky_group()))
+            .flat_map(|class| class.combos())
+            .collect()
+    }
+
+    /// Adds the combo at full weight, returning `false` if it was already
+    /// present or holds the same card twice.
+    pub fn insert(&mut self, combo: [Card; 2]) -> bool {
+        if combo[0] == combo[1] || self.contains(combo) {
+            return false;
+        }
+        self.combos.insert(normalize(combo), 1.0);
+        true
+    }
+
+    pub fn contains(&self, combo: [Card; 2]) -> bool {
+        self.combos.contains_key(&normalize(combo))
+    }
+
+    /// The combo's weight, 0 when it is not in the range.
+    pub fn weight(&self, combo: [Card; 2]) -> f64 {
+        self.combos.get(&normalize(combo)).copied().unwrap_or(0.0)
+    }
+
+    /// Sets the combo's weight, clamped to `0..=1`; a weight of 0 removes
+    /// it. A combo holding the same card twice is ignored.
+    pub fn set_combo_weight(&mut self, combo: [Card; 2], weight: f64) {
+        if combo[0] == combo[1] {
+            return;
+        }
+        if weight > 0.0 {
+            self.combos.insert(normalize(combo), weight.min(1.0));
+        } else {
+            self.combos.remove(&normalize(combo));
+        }
+    }
+
+    /// Sets the weight of every combo of the class, see `set_combo_weight`.
+    pub fn set_weight(&mut self, class: HoleClass, weight: f64) {
+        for combo in class.combos() {
+            self.set_combo_weight(combo, weight);
+        }
+    }
+
+    /// The combos, higher card first, in ascending order.
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        self.iter().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = [Card; 2]> + '_ {
+        self.combos.keys().copied()
+    }
+
+    /// The combos in the order of `iter`, with their weights.
+    pub fn iter_weighted(&self) -> impl Iterator<Item = ([Card; 2], f64)> + '_ {
+        self.combos.iter().map(|(&combo, &weight)| (combo, weight))
+    }
+
+    /// Number of combos holding no dead card, each counted by its weight.
+    pub fn count_combos(&self, dead: &CardSet) -> f64 {
+        self.iter_weighted()
+            .filter(|&(combo, _)| is_live(combo, dead))
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+
+    /// The combos holding no dead card, in the order of `combos`.
+    pub fn live_combos(&self, dead: &CardSet) -> Vec<[Card; 2]> {
+        self.iter().filter(|&combo| is_live(combo, dead)).collect()
+    }
+
+    /// `count_combos` per hole class, for the classes with any left.
+    pub fn count_by_class(&self, dead: &CardSet) -> BTreeMap<HoleClass, f64> {
+        let mut counts = BTreeMap::new();
+        for (combo, weight) in self.iter_weighted() {
+            if is_live(combo, dead) {
+                *counts.entry(HoleClass::from_cards(combo)).or_insert(0.0) += weight;
+            }
+        }
+        counts
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+}
+
+// the item without its weight, and the weight as a fraction
+fn split_weight(item: &str) -> Option<(&str, f64)> {
+    let (body, percent) = if let Some((body, percent)) = item.split_once('@') {
+        (body, percent)
+    } else if let Some(rest) = item.strip_prefix('[') {
+        let (percent, body) = rest.split_once(']')?;
+        (body, percent)
+    } else {
+        return Some((item, 1.0));
+    };
+    let percent: f64 = percent.trim().parse().ok()?;
+    (0.0..=100.0)
+        .contains(&percent)
+        .then_some((body.trim(), percent / 100.0))
+}
+
+fn parse_item(item: &str) -> Option<Vec<[Card; 2]>> {
+    let classes = if let Some((from, to)) = item.split_once('-') {
+        span(shape(from.trim())?, shape(to.trim())?)?
+    } else if let Some(base) = item.strip_suffix('+') {
+        let (high, low, suffix) = shape(base)?;
+        if high == low {
+            Value::range(low, Value::Ace)
+                .flat_map(|v| classes(v, v, None))
+                .collect()
+        } else {
+            Value::range_exclusive(low, high)
+                .flat_map(|v| classes(high, v, suffix))
+                .collect()
+        }
+    } else if let Some(combo) = combo(item) {
+        return Some(vec![combo]);
+    } else {
+        let (high, low, suffix) = shape(item)?;
+        classes(high, low, suffix)
+    };
+    Some(classes.iter().flat_map(HoleClass::combos).collect())
+}
+
+// two values, higher first, and the `s` or `o` suffix if any
+fn shape(token: &str) -> Option<(Value, Value, Option<char>)> {
+    let chars: Vec<char> = token.chars().collect();
+    let (a, b) = match chars[..] {
+        [a, b] | [a, b, _] => (Value::try_from(a).ok()?, Value::try_from(b).ok()?),
+        _ => return None,
+    };
+    let suffix = chars.get(2).map(char::to_ascii_lowercase);
+    match suffix {
+        None => {}
+        Some('s' | 'o') if a != b => {}
+        _ => return None,
+    }
+    Some((a.max(b), a.min(b), suffix))
+}
+
+fn classes(high: Value, low: Value, suffix: Option<char>) -> Vec<HoleClass> {
+    let suited = HoleClass::suited(high, low);
+    let offsuit = HoleClass::offsuit(high, low);
+    match suffix {
+        _ if high == low => vec![HoleClass::pair(high)],
+        Some('s') => suited.into_iter().collect(),
+        Some('o') => offsuit.into_iter().collect(),
+        _ => suited.into_iter().chain(offsuit).collect(),
+    }
+}
+
+fn span(
+    from: (Value, Value, Option<char>),
+    to: (Value, Value, Option<char>),
+) -> Option<Vec<HoleClass>> {
+    let ((high, low, suffix), (to_high, to_low, to_suffix)) = (from.min(to), from.max(to));
+    let pairs = high == low;
+    if suffix != to_suffix || pairs != (to_high == to_low) {
+        return None;
+    }
+    // pairs and same-high spans move the low card only, others keep the gap
+    let fixed_high = pairs || high == to_high;
+    if !fixed_high && high.gap(low) != to_high.gap(to_low) {
+        return None;
+    }
+    let steps = to_low.value() - low.value();
+    let mut spanned = Vec::new();
+    for step in 0..=steps {
+        let l = low.checked_add_rank(step)?;
+        let h = match (pairs, fixed_high) {
+            (true, _) => l,
+            (false, true) => high,
+            (false, false) => high.checked_add_rank(step)?,
+        };
+        spanned.extend(classes(h, l, suffix));
+    }
+    Some(spanned)
+}
+
+// two cards written together, e.g. `AhKh`
+fn combo(token: &str) -> Option<[Card; 2]> {
+    let chars: Vec<char> = token.chars().collect();
+    match chars[..] {
+        [v1, s1, v2, s2] => {
+            let (a, b) = (
+                Card::try_from((v1, s1)).ok()?,
+                Card::try_from((v2, s2)).ok()?,
+            );
+            (a != b).then_some([a, b])
+        }
+        _ => None,
+    }
+}
+
+fn is_live([a, b]: [Card; 2], dead: &CardSet) -> bool {
+    !dead.contains(a) && !dead.contains(b)
+}
+
+fn normalize([a, b]: [Card; 2]) -> [Card; 2] {
+    if a > b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+impl From<[Card; 2]> for Range {
+    fn from(combo: [Card; 2]) -> Self {
+        Range::from_iter([combo])
+    }
+}
+
+impl FromIterator<[Card; 2]> for Range {
+    fn from_iter<T: IntoIterator<Item = [Card; 2]>>(iter: T) -> Self {
+        let mut range = Range::new();
+        range.extend(iter);
+        range
+    }
+}
+
+impl Extend<[Card; 2]> for Range {
+    fn extend<T: IntoIterator<Item = [Card; 2]>>(&mut self, iter: T) {
+        for combo in iter {
+            self.insert(combo);
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Writes the range back in `parse` notation, strongest items first:
+/// whole classes of one weight joined into `+` and `-` runs where
+/// possible, then any combos left over. Weights other than 1 follow as
+/// `@` and a percentage to two decimal places.
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // classes with every combo present at the same weight
+        let full: Vec<(HoleClass, f64)> = HoleClass::all()
+            .filter_map(|class| {
+                let mut weights = class.combos().into_iter().map(|c| self.weight(c));
+                let weight = weights.next()?;
+                (weight > 0.0 && weights.all(|w| w == weight)).then_some((class, weight))
+            })
+            .collect();
+        let covered: BTreeSet<[Card; 2]> = full.iter().flat_map(|(c, _)| c.combos()).collect();
+
+        // runs down the kicker for one high card (pairs count as one run),
+        // then what is left over joined into runs keeping the same gap
+        let mut kicker_runs: Vec<Vec<(HoleClass, f64)>> = Vec::new();
+        for (class, weight) in full {
+            match kicker_runs.last_mut() {
+                Some(run) if follows_kicker(run[run.len() - 1], (class, weight)) => {
+                    run.push((class, weight))
+                }
+                _ => kicker_runs.push(vec![(class, weight)]),
+            }
+        }
+        let (mut runs, singles): (Vec<_>, Vec<_>) =
+            kicker_runs.into_iter().partition(|run| run.len() > 1);
+        let mut gap_runs: Vec<Vec<(HoleClass, f64)>> = Vec::new();
+        for class in singles.into_iter().flatten() {
+            match gap_runs
+                .iter_mut()
+                .find(|r| follows_gap(r[r.len() - 1], class))
+            {
+                Some(run) => run.push(class),
+                None => gap_runs.push(vec![class]),
+            }
+        }
+        runs.extend(gap_runs);
+        runs.sort_by(|a, b| b[0].0.cmp(&a[0].0));
+
+        let mut items: Vec<String> = runs.iter().map(|run| run_item(run)).collect();
+        for (&[a, b], &weight) in self.combos.iter().rev() {
+            if !covered.contains(&[a, b]) {
+                let combo = format!(
+                    "{}{}{}{}",
+                    a.value().to_char(),
+                    a.suit().to_char(),
+                    b.value().to_char(),
+                    b.suit().to_char()
+                );
+                items.push(weighted_item(combo, weight));
+            }
+        }
+        f.pad(&items.join(", "))
+    }
+}
+
+fn follows_kicker(
+    (prev, prev_weight): (HoleClass, f64),
+    (class, weight): (HoleClass, f64),
+) -> bool {
+    prev_weight == weight
+        && prev.low().prev() == Some(class.low())
+        && prev.is_pair() == class.is_pair()
+        && (class.is_pair()
+            || (prev.high() == class.high() && prev.is_suited() == class.is_suited()))
+}
+
+fn follows_gap((prev, prev_weight): (HoleClass, f64), (class, weight): (HoleClass, f64)) -> bool {
+    prev_weight == weight
+        && !class.is_pair()
+        && prev.high().prev() == Some(class.high())
+        && prev.low().prev() == Some(class.low())
+        && prev.is_suited() == class.is_suited()
+}
+
+fn run_item(run: &[(HoleClass, f64)]) -> String {
+    let ((top, weight), (bottom, _)) = (run[0], run[run.len() - 1]);
+    let open_ended = if top.is_pair() {
+        top.high() == Value::Ace
+    } else {
+        top.high().prev() == Some(top.low()) && top.high() == bottom.high()
+    };
+    let item = match run.len() {
+        1 => top.to_string(),
+        _ if open_ended => format!("{}+", bottom),
+        _ => format!("{}-{}", top, bottom),
+    };
+    weighted_item(item, weight)
+}
+
+fn weighted_item(item: String, weight: f64) -> String {
+    if weight == 1.0 {
+        return item;
+    }
+    let percent = (weight * 10_000.0).round() / 100.0;
+    format!("{}@{}", item, percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(s: &str) -> [Card; 2] {
+        Card::parse_many(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_range() {
+        let range: Range = [
+            combo("Kd As"),
+            combo("As Kd"),
+            combo("2c 2d"),
+            combo("2c 2c"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(range.len(), 2);
+        assert!(range.contains(combo("Kd As")));
+        assert!(range.contains(combo("2d 2c")));
+        assert!(!range.contains(combo("Ks As")));
+        assert_eq!(range.combos(), [combo("2c 2d"), combo("As Kd")]);
+        assert!(Range::new().is_empty());
+        assert_eq!(Range::from(combo("Qh Jh")).len(), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        let len = |s: &str| Range::parse(s).unwrap().len();
+        assert_eq!(len("22+"), 78);
+        assert_eq!(len("TT+"), 30);
+        assert_eq!(len("ATs+"), 16);
+        assert_eq!(len("AK"), 16);
+        assert_eq!(len("KQo"), 12);
+        assert_eq!(len("65s-87s"), 12);
+        assert_eq!(len("87s - 65s"), 12);
+        assert_eq!(len("A2s-A5s"), 16);
+        assert_eq!(len("55-22"), 24);
+        assert_eq!(len("ahkh"), 1);
+        assert_eq!(len(""), 0);
+        assert!(Range::parse("AhKh").unwrap().contains(combo("Kh Ah")));
+        assert!(!Range::parse("ATs+").unwrap().contains(combo("As 9s")));
+        assert!(Range::parse("65s-87s").unwrap().contains(combo("7d 6d")));
+
+        // overlapping items count each combo once
+        assert_eq!(len("22+, TT+, AA, AsAh"), 78);
+        assert_eq!(len("AK, AKs, AKo+, AsKs"), 16);
+
+        let random = "22+, 32+, 42+, 52+, 62+, 72+, 82+, 92+, T2+, J2+, Q2+, K2+, A2+";
+        assert_eq!(len(random), 1326);
+        assert_eq!("22+, A2+".parse::<Range>().unwrap().len(), 78 + 12 * 16);
+
+        for (s, bad) in [
+            ("AAs", "AAs"),
+            ("22+, ATx, KQo", "ATx"),
+            ("65s-87o", "65s-87o"),
+            ("65s-A2s", "65s-A2s"),
+            ("22-AKs", "22-AKs"),
+            ("AhAh", "AhAh"),
+            ("AKs++", "AKs++"),
+            ("1", "1"),
+        ] {
+            assert_eq!(Range::parse(s), Err(Error::BadRange(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let show = |s: &str| Range::parse(s).unwrap().to_string();
+        assert_eq!(show("TT+, ATs+, 65s-87s, KQo"), "TT+, ATs+, KQo, 87s-65s");
+        assert_eq!(
+            show("22-55, A5s-A2s, 7d2c, AhKh"),
+            "55-22, A5s-A2s, AhKh, 7d2c"
+        );
+        assert_eq!(show("77, KJs, AKo, AKs"), "77, AKs, AKo, KJs");
+        assert_eq!(show("AKs, KQs, QJs, QTs"), "AKs-KQs, QTs+");
+        assert_eq!(show("AsKs, AhKh, AdKd, AcKc"), "AKs");
+        assert_eq!(show(""), "");
+
+        for s in [
+            "22+, 32+, 42+, 52+, 62+, 72+, 82+, 92+, T2+, J2+, Q2+, K2+, A2+",
+            "TT-66, AJs+, KQ, T9s-54s, AcKd, 2c2d",
+            "A2s, A4s, A6s, K9o, 98o, 87o",
+        ] {
+            let range = Range::parse(s).unwrap();
+            assert_eq!(
+                Range::parse(&range.to_string()).unwrap(),
+                range,
+                "{}",
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn test_top_percent() {
+        assert_eq!(Range::top_percent(100.0).len(), 1326);
+        assert_eq!(Range::top_percent(250.0).len(), 1326);
+        assert!(Range::top_percent(0.0).is_empty());
+        assert!(Range::top_percent(f64::NAN).is_empty());
+        assert_eq!(Range::top_percent(0.5), Range::parse("AA").unwrap());
+        assert_eq!(Range::top_percent(2.0), Range::parse("JJ+").unwrap());
+
+        let mut previous = Range::new();
+        for step in 0..=200 {
+            let range = Range::top_percent(step as f64 / 2.0);
+            assert!(previous.iter().all(|c| range.contains(c)), "{}", step);
+            previous = range;
+        }
+    }
+
+    #[test]
+    fn test_dead_cards() {
+        let dead = |s: &str| -> CardSet { Card::parse_many(s).unwrap().into_iter().collect() };
+        let aces = Range::parse("AA").unwrap();
+        assert_eq!(aces.count_combos(&CardSet::new()), 6.0);
+        assert_eq!(aces.count_combos(&dead("As")), 3.0);
+        assert_eq!(aces.count_combos(&dead("As Ah")), 1.0);
+        assert_eq!(aces.live_combos(&dead("As Ah")), [combo("Ac Ad")]);
+        assert_eq!(Range::parse("AKs").unwrap().count_combos(&dead("Kh")), 3.0);
+        assert_eq!(Range::parse("AK").unwrap().count_combos(&dead("As")), 12.0);
+
+        let random = Range::top_percent(100.0);
+        let board = dead("Ah Kd 7c 7s 2h");
+        assert_eq!(random.count_combos(&board), 1081.0);
+        assert!(random
+            .live_combos(&board)
+            .iter()
+            .all(|c| !board.contains(c[0]) && !board.contains(c[1])));
+
+        let counts = Range::parse("AA, AKs, KQo")
+            .unwrap()
+            .count_by_class(&dead("As Kh"));
+        let class = |s: &str| s.parse::<HoleClass>().unwrap();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            [(class("KQo"), 9.0), (class("AKs"), 2.0), (class("AA"), 3.0)]
+        );
+        let counts = random.count_by_class(&CardSet::new());
+        assert_eq!(counts.len(), 169);
+        assert!(counts
+            .iter()
+            .all(|(class, &n)| n == class.combo_count() as f64));
+    }
+
+    #[test]
+    fn test_weights() {
+        let range = Range::parse("AA@50, [25]KK, QQ, AKs@100, AKo@0").unwrap();
+        assert_eq!(range.weight(combo("As Ah")), 0.5);
+        assert_eq!(range.weight(combo("Ks Kh")), 0.25);
+        assert_eq!(range.weight(combo("Qs Qh")), 1.0);
+        assert_eq!(range.weight(combo("Ks Ah")), 0.0);
+        assert_eq!(range.len(), 22);
+        assert_eq!(range.count_combos(&CardSet::new()), 3.0 + 1.5 + 6.0 + 4.0);
+        assert_eq!(range.to_string(), "AA@50, KK@25, QQ, AKs");
+
+        // the last item naming a combo sets its weight
+        let range = Range::parse("TT+@50, AA, AsAh@10").unwrap();
+        assert_eq!(
+            range.to_string(),
+            "KK-TT@50, AsAc, AsAd, AsAh@10, AcAd, AcAh, AdAh"
+        );
+        let round_trip = Range::parse(&range.to_string()).unwrap();
+        assert_eq!(round_trip, range);
+
+        let mut range = Range::parse("AK").unwrap();
+        let class = |s: &str| s.parse::<HoleClass>().unwrap();
+        range.set_weight(class("AKo"), 0.333);
+        range.set_weight(class("QJs"), 2.0);
+        range.set_combo_weight(combo("Ah Kh"), 0.0);
+        assert_eq!(range.to_string(), "AKo@33.3, QJs, AsKs, AcKc, AdKd");
+        let count = range.count_by_class(&CardSet::new())[&class("AKo")];
+        assert!((count - 12.0 * 0.333).abs() < 1e-9);
+        assert!(!range.contains(combo("Ah Kh")));
+        assert_eq!(range.iter_weighted().count(), range.len());
+
+        for bad in [
+            "AA@",
+            "AA@101",
+            "AA@-5",
+            "[50AA",
+            "[x]AA",
+            "AA@50@50",
+            "[50]AA@50",
+        ] {
+            assert_eq!(Range::parse(bad), Err(Error::BadRange(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_sklansky_groups() {
+        let top = Range::from_sklansky_groups(1..=3);
+        assert_eq!(
+            top,
+            Range::parse("99+, ATs+, KJs+, QJs, JTs, AQo+").unwrap()
+        );
+        assert_eq!(Range::from_sklansky_groups(1..=1).to_string(), "JJ+, AKs");
+        assert_eq!(Range::from_sklansky_groups(..).len(), 1326);
+        assert!(Range::from_sklansky_groups(9..).is_empty());
+    }
+}
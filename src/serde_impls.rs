@@ -0,0 +1,286 @@
+use std::{fmt::Formatter, marker::PhantomData};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    card::{Card, CardKind, Joker, Suit, Value},
+    error::Error,
+    holdem::{HoldemHand, Rank, RankCategory},
+};
+
+/// Deserializes any type that parses from its `Display` string.
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for StrVisitor<T>
+where
+    T: for<'a> TryFrom<&'a str, Error = Error>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a string like \"As\" or \"As Ks Qs Js 10s\"")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::try_from(v).map_err(E::custom)
+    }
+}
+
+macro_rules! impl_serde_as_str {
+    ($($t:ty),*) => {$(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(StrVisitor(PhantomData))
+            }
+        }
+    )*};
+}
+
+impl_serde_as_str!(Suit, Value, Joker, CardKind);
+
+/// Hands are their card string (`"As Ks Qs Js 10s"`) in human-readable
+/// formats and five `Card::to_byte` bytes in binary ones. Either way the
+/// rank is recomputed when reading.
+impl Serialize for HoldemHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.cards().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HoldemHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor(PhantomData))
+        } else {
+            let cards = <[Card; 5]>::deserialize(deserializer)?;
+            HoldemHand::new(cards).map_err(de::Error::custom)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Rank")]
+struct RankRepr {
+    category: String,
+    values: Vec<Value>,
+}
+
+/// Ranks are a `{ category, values }` struct, e.g.
+/// `{"category":"full_house","values":["K","10"]}`.
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RankRepr {
+            category: self.category().as_str().to_string(),
+            values: self.values().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RankRepr::deserialize(deserializer)?;
+        let category: RankCategory = repr.category.parse().map_err(de::Error::custom)?;
+        Rank::from_parts(category, &repr.values)
+            .filter(|rank| rank.representative().is_some())
+            .ok_or_else(|| de::Error::custom(Error::BadRank))
+    }
+}
+
+/// Cards are strings (`"As"`) in human-readable formats and a single
+/// `Card::to_byte` byte in binary ones like bincode.
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u8(self.to_byte())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_u8(ByteVisitor)
+        }
+    }
+}
+
+struct ByteVisitor;
+
+impl<'de> de::Visitor<'de> for ByteVisitor {
+    type Value = Card;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a packed card byte")
+    }
+
+    fn visit_u8<E: de::Error>(self, v: u8) -> Result<Self::Value, E> {
+        Card::from_byte(v).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_json() {
+        let card = Card::try_from("As").unwrap();
+        assert_eq!(serde_json::to_string(&card).unwrap(), "\"As\"");
+        assert_eq!(serde_json::from_str::<Card>("\"As\"").unwrap(), card);
+        assert_eq!(serde_json::to_string(&Suit::Heart).unwrap(), "\"h\"");
+        assert_eq!(serde_json::from_str::<Value>("\"10\"").unwrap(), Value::Ten);
+        assert_eq!(serde_json::to_string(&Joker::Big).unwrap(), "\"bj\"");
+        assert_eq!(
+            serde_json::from_str::<Joker>("\"sj\"").unwrap(),
+            Joker::Small
+        );
+
+        for suit in Suit::ALL {
+            for value in Value::ALL {
+                let card = Card::new(suit, value);
+                let json = serde_json::to_string(&card).unwrap();
+                assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_kind_json() {
+        let kinds = vec![
+            CardKind::from(Joker::Big),
+            Card::try_from("As").unwrap().into(),
+        ];
+        let json = serde_json::to_string(&kinds).unwrap();
+        assert_eq!(json, "[\"bj\",\"As\"]");
+        assert_eq!(serde_json::from_str::<Vec<CardKind>>(&json).unwrap(), kinds);
+    }
+
+    #[test]
+    fn test_hand_json() {
+        let hand = HoldemHand::try_from("As Ks Qs Js 10s").unwrap();
+        let json = serde_json::to_string(&hand).unwrap();
+        assert_eq!(json, "\"As Ks Qs Js 10s\"");
+        let parsed = serde_json::from_str::<HoldemHand>(&json).unwrap();
+        assert_eq!(parsed.cards(), hand.cards());
+
+        let cards = vec![
+            Card::try_from("2c").unwrap(),
+            Card::try_from("10h").unwrap(),
+        ];
+        let json = serde_json::to_string(&cards).unwrap();
+        assert_eq!(json, "[\"2c\",\"10h\"]");
+        assert_eq!(serde_json::from_str::<Vec<Card>>(&json).unwrap(), cards);
+    }
+
+    #[test]
+    fn test_card_bincode() {
+        for card in Card::all() {
+            let bytes = bincode::serialize(&card).unwrap();
+            assert_eq!(bytes, [card.to_byte()]);
+            assert_eq!(bincode::deserialize::<Card>(&bytes).unwrap(), card);
+        }
+
+        let cards = [
+            Card::try_from("As").unwrap(),
+            Card::try_from("Kd").unwrap(),
+            Card::try_from("10h").unwrap(),
+            Card::try_from("7c").unwrap(),
+            Card::try_from("2s").unwrap(),
+        ];
+        let bytes = bincode::serialize(&cards).unwrap();
+        assert_eq!(bytes.len(), 5);
+        assert_eq!(bincode::deserialize::<[Card; 5]>(&bytes).unwrap(), cards);
+        // a Vec adds the u64 length prefix
+        assert_eq!(bincode::serialize(&cards.to_vec()).unwrap().len(), 8 + 5);
+
+        let err = bincode::deserialize::<Card>(&[0]).unwrap_err();
+        assert!(err.to_string().contains("invalid byte 0"));
+        assert!(bincode::deserialize::<Card>(&[255]).is_err());
+    }
+
+    fn ranked(s: &str) -> HoldemHand {
+        HoldemHand::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn test_hand_bincode() {
+        for s in [
+            "As Ks Qs Js 10s",
+            "5h 4h 3h 2h Ah",
+            "9c 9d 9h 2s 2c",
+            "Kd Jd 7c 4s 2h",
+        ] {
+            let hand = ranked(s);
+            let bytes = bincode::serialize(&hand).unwrap();
+            assert_eq!(bytes, hand.cards().map(|c| c.to_byte()));
+            let back = bincode::deserialize::<HoldemHand>(&bytes).unwrap();
+            assert_eq!(back.cards(), hand.cards());
+            assert_eq!(back.rank(), hand.rank());
+        }
+        let mut bytes = bincode::serialize(&ranked("As Ks Qs Js 10s")).unwrap();
+        bytes[1] = bytes[0];
+        let err = bincode::deserialize::<HoldemHand>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("Duplicate card: As"));
+    }
+
+    #[test]
+    fn test_rank_serde() {
+        let json = serde_json::to_string(&ranked("Kh Kd Kc 10s 10h").rank()).unwrap();
+        assert_eq!(json, r#"{"category":"full_house","values":["K","10"]}"#);
+        let json = serde_json::to_string(&ranked("As Ks Qs Js 10s").rank()).unwrap();
+        assert_eq!(json, r#"{"category":"royal_flush","values":[]}"#);
+
+        for s in [
+            "As Ks Qs Js 10s",
+            "5h 4h 3h 2h Ah",
+            "6h 6d 6c 6s Ah",
+            "Ah Ad 3c 3s 9h",
+            "7h 5d 4c 3s 2h",
+            "Ah 2d 3c 4s 5h",
+        ] {
+            let rank = ranked(s).rank();
+            let json = serde_json::to_string(&rank).unwrap();
+            assert_eq!(serde_json::from_str::<Rank>(&json).unwrap(), rank);
+            let bytes = bincode::serialize(&rank).unwrap();
+            assert_eq!(bincode::deserialize::<Rank>(&bytes).unwrap(), rank);
+        }
+
+        for bad in [
+            r#"{"category":"boat","values":["K","10"]}"#,
+            r#"{"category":"full_house","values":["K","K"]}"#,
+            r#"{"category":"full_house","values":["K"]}"#,
+            r#"{"category":"straight","values":["4"]}"#,
+        ] {
+            let err = serde_json::from_str::<Rank>(bad).unwrap_err();
+            assert!(err.to_string().contains("Bad rank"), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let err = serde_json::from_str::<Card>("\"Ax\"").unwrap_err();
+        assert!(err.to_string().contains("Bad suit: x"));
+        let err = serde_json::from_str::<Card>("\"1s\"").unwrap_err();
+        assert!(err.to_string().contains("Bad value: 1"));
+        let err = serde_json::from_str::<HoldemHand>("\"As Ks\"").unwrap_err();
+        assert!(err.to_string().contains("invalid number of cards"));
+        assert!(serde_json::from_str::<Card>("42").is_err());
+        assert!(serde_json::from_str::<Joker>("\"Medium\"").is_err());
+        assert!(serde_json::from_str::<CardKind>("\"xj\"").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! proptest strategies for cards, enabled by the `proptest` feature.
+
+use proptest::{
+    arbitrary::Arbitrary,
+    sample::{select, subsequence},
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::card::{Card, Suit, Value};
+
+impl Arbitrary for Suit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Suit>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(Suit::ALL.to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Value {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Value>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(Value::ALL.to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Card {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Card>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(Card::ALL.to_vec()).boxed()
+    }
+}
+
+/// `n` distinct cards in random order, e.g. a board.
+pub fn distinct_cards(n: usize) -> impl Strategy<Value = Vec<Card>> {
+    subsequence(Card::ALL.to_vec(), n).prop_shuffle()
+}
+
+/// Five distinct cards, ready to build a hold'em hand from.
+pub fn five_cards() -> impl Strategy<Value = [Card; 5]> {
+    distinct_cards(5).prop_map(|cards| std::array::from_fn(|i| cards[i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card::has_duplicates, holdem::HoldemHand};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_card_roundtrip(card: Card) {
+            prop_assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+            prop_assert_eq!(Card::from_index(card.to_index()), Ok(card));
+        }
+
+        #[test]
+        fn test_suit_value_roundtrip(suit: Suit, value: Value) {
+            prop_assert_eq!(suit.to_string().parse::<Suit>(), Ok(suit));
+            prop_assert_eq!(value.to_string().parse::<Value>(), Ok(value));
+        }
+
+        #[test]
+        fn test_distinct_cards(board in distinct_cards(7)) {
+            prop_assert_eq!(board.len(), 7);
+            prop_assert!(!has_duplicates(&board));
+        }
+
+        #[test]
+        fn test_five_cards(cards in five_cards()) {
+            prop_assert!(!has_duplicates(&cards));
+            let hand = HoldemHand::new(cards).unwrap();
+            prop_assert_eq!(hand.to_string().parse::<HoldemHand>(), Ok(hand));
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use poker::{card::Card, Error, HoldemHand, Rank};
+
+#[test]
+fn test_royal_flush() {
+    let hand: HoldemHand = "As Ks Qs Js 10s".parse().unwrap();
+    assert!(matches!(hand.rank(), Rank::RoyalStraightFlush));
+    assert_eq!(hand.cards()[0], Card::try_from("As").unwrap());
+    assert_eq!(poker::hand!("10s Js Qs Ks As").cards(), hand.cards());
+}
+
+#[test]
+fn test_new() {
+    let cards = Card::parse_many("2c 9h 9d Kc 2s").unwrap();
+    let hand = HoldemHand::new([cards[0], cards[1], cards[2], cards[3], cards[4]]).unwrap();
+    assert_eq!(hand.to_string(), "Kc 9h 9d 2c 2s");
+    match hand.rank() {
+        Rank::TwoPair([high, low, kicker]) => {
+            assert_eq!(high.to_string(), "9");
+            assert_eq!(low.to_string(), "2");
+            assert_eq!(kicker.to_string(), "K");
+        }
+        rank => panic!("unexpected rank {:?}", rank),
+    }
+}
+
+#[test]
+fn test_duplicate_card() {
+    let cards = Card::parse_many("2c 9h 9d Kc 2c").unwrap();
+    match HoldemHand::new([cards[0], cards[1], cards[2], cards[3], cards[4]]) {
+        Err(Error::DuplicateCard(card)) => assert_eq!(card, cards[0]),
+        result => panic!("unexpected result {:?}", result),
+    }
+    let err = HoldemHand::best_from(&cards);
+    assert!(matches!(err, Err(Error::DuplicateCard(_))));
+}